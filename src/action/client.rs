@@ -1,4 +1,6 @@
-use std::{ffi::CString, marker::PhantomData, mem::MaybeUninit, sync::Arc, time::Duration};
+use std::{
+    ffi::CString, future::Future, marker::PhantomData, mem::MaybeUninit, sync::Arc, time::Duration,
+};
 
 use crate::{
     error::{DynError, RCLActionError, RCLActionResult, RCLError},
@@ -56,6 +58,27 @@ impl Drop for ClientData {
     }
 }
 
+impl ClientData {
+    fn is_server_available(&self) -> RCLActionResult<bool> {
+        let guard = rcl::MT_UNSAFE_FN.lock();
+
+        let mut is_available = false;
+        match guard.rcl_action_server_is_available(
+            self.node.as_ptr(),
+            &self.client,
+            &mut is_available as *mut _,
+        ) {
+            Ok(()) => Ok(is_available),
+            Err(RCLActionError::RCLError(RCLError::NodeInvalid)) => {
+                // TODO: soft failure in case of shutdown context
+                eprintln!("Invalid node (the shutdown has started?)");
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
 unsafe impl Sync for ClientData {}
 unsafe impl Send for ClientData {}
 
@@ -99,22 +122,22 @@ where
     }
 
     pub fn is_server_available(&self) -> RCLActionResult<bool> {
-        let guard = rcl::MT_UNSAFE_FN.lock();
+        self.data.is_server_available()
+    }
 
-        let mut is_available = false;
-        match guard.rcl_action_server_is_available(
-            self.data.node.as_ptr(),
-            &self.data.client,
-            &mut is_available as *mut _,
-        ) {
-            Ok(()) => Ok(is_available),
-            Err(RCLActionError::RCLError(RCLError::NodeInvalid)) => {
-                // TODO: soft failure in case of shutdown context
-                eprintln!("Invalid node (the shutdown has started?)");
-                Ok(false)
-            }
-            Err(e) => Err(e),
-        }
+    /// Block until the action server becomes available or `timeout` elapses.
+    /// This returns `false` if timed out.
+    pub fn wait_for_server(&self, timeout: Duration) -> Result<bool, DynError> {
+        self.data
+            .node
+            .wait_graph(timeout, || Ok(self.data.is_server_available()?))
+    }
+
+    /// Wait until the action server becomes available asynchronously.
+    pub fn wait_for_server_async(&self) -> impl Future<Output = Result<(), DynError>> {
+        let data = self.data.clone();
+        let node = data.node.clone();
+        node.wait_graph_async(move || Ok(data.is_server_available()?))
     }
 
     // Send a goal request to the server. the UUID are automatically attached.
@@ -1,6 +1,9 @@
 //! Context of ROS2.
 //! A context can create `Node` and `Selector`.
 //!
+//! `Context::new` returns the context shared in the process.
+//! Use `ContextBuilder` to create independent contexts with explicit arguments and options.
+//!
 //! # Example
 //!
 //! ```
@@ -17,28 +20,84 @@
 //! // Create a selector.
 //! let selector = ctx.create_selector().unwrap();
 //! ```
+//!
+//! # Shutdown
+//!
+//! A context can be shut down explicitly by `Context::shutdown`.
+//! Callbacks registered by `Context::add_on_shutdown` are invoked before shutting down.
+//! After that, `Selector::wait` and asynchronous receivers of the context
+//! return `ContextShutdown` as an error.
+//!
+//! ```
+//! use safe_drive::context::ContextBuilder;
+//!
+//! let ctx = ContextBuilder::new().build().unwrap();
+//!
+//! ctx.add_on_shutdown(Box::new(|| println!("flush state")));
+//!
+//! ctx.shutdown().unwrap();
+//! assert!(!ctx.is_valid());
+//! ```
 
 use crate::{
     error::*,
     get_allocator,
     node::{Node, NodeOptions},
     rcl,
-    selector::{async_selector::SELECTOR, Selector},
+    selector::{async_selector::SELECTOR, guard_condition::RCLGuardCondition, Selector},
     signal_handler,
+    topic::intra_process::IntraProcessManager,
 };
 use once_cell::sync::Lazy;
 use parking_lot::Mutex;
-use std::{env, ffi::CString, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    env,
+    error::Error,
+    ffi::CString,
+    fmt::Display,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Weak,
+    },
+};
 
 static CONTEXT: Lazy<Mutex<Option<Arc<Context>>>> = Lazy::new(|| Mutex::new(None));
 
+type ShutdownCallback = Box<dyn FnOnce() + Send + 'static>;
+
+/// Error returned by functions to wait or receive when the context has been shut down.
+#[derive(Debug)]
+pub struct ContextShutdown;
+
+impl Display for ContextShutdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "context has been shut down")
+    }
+}
+
+impl Error for ContextShutdown {}
+
+#[derive(Default)]
+struct OnShutdown {
+    callbacks: BTreeMap<u64, ShutdownCallback>,
+    next_id: u64,
+}
+
 /// Context of ROS2.
 pub struct Context {
     context: rcl::rcl_context_t,
+    is_shutdown: AtomicBool,
+    on_shutdown: Mutex<OnShutdown>,
+    wakeup_conds: Mutex<BTreeMap<*const rcl::rcl_guard_condition_t, Weak<RCLGuardCondition>>>,
+    pub(crate) intra_process: IntraProcessManager,
 }
 
 impl Context {
     /// Create a new context.
+    /// The context is initialized by `std::env::args()` and the default options,
+    /// and it is cached and shared in the process.
+    /// So, calling `new` twice returns the same context.
     ///
     /// # Example
     ///
@@ -58,15 +117,45 @@ impl Context {
             }
         }
 
+        let args: Vec<_> = env::args().collect();
+        let options = InitOptions::new()?;
+        let context = Arc::new(Context::init(&args, &options)?);
+        {
+            let mut guard = CONTEXT.lock();
+            *guard = Some(context.clone());
+        }
+
+        Ok(context)
+    }
+
+    /// Create a builder to create a context with explicit arguments and options.
+    ///
+    /// Unlike `Context::new`, every context created by the builder is independent
+    /// of the others and of the global context.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::context::Context;
+    ///
+    /// let ctx = Context::builder().domain_id(5).build().unwrap();
+    /// assert_eq!(ctx.get_domain_id().unwrap(), 5);
+    /// ```
+    pub fn builder() -> ContextBuilder {
+        ContextBuilder::new()
+    }
+
+    fn init(args: &[String], options: &InitOptions) -> Result<Self, DynError> {
         // allocate context
         let mut context = rcl::MTSafeFn::rcl_get_zero_initialized_context();
 
         // convert Args to Vec<*const c_ptr>
-        let cstr_args: Vec<_> = env::args().map(|s| CString::new(s).unwrap()).collect();
+        let cstr_args = args
+            .iter()
+            .map(|s| CString::new(s.as_str()))
+            .collect::<Result<Vec<_>, _>>()?;
         let args: Vec<_> = cstr_args.iter().map(|s| s.as_ptr()).collect();
 
-        let options = InitOptions::new()?;
-
         {
             let guard = rcl::MT_UNSAFE_FN.lock();
 
@@ -82,13 +171,109 @@ impl Context {
             guard.rcl_logging_fini()?;
         }
 
-        let context = Arc::new(Context { context });
+        Ok(Context {
+            context,
+            is_shutdown: AtomicBool::new(false),
+            on_shutdown: Default::default(),
+            wakeup_conds: Default::default(),
+            intra_process: Default::default(),
+        })
+    }
+
+    /// Return `true` if the context is valid; it has not been shut down yet.
+    pub fn is_valid(&self) -> bool {
+        rcl::MTSafeFn::rcl_context_is_valid(self.as_ptr())
+    }
+
+    /// Register a callback function invoked when the context is shut down.
+    /// Callbacks are invoked in order of registration,
+    /// and the context is still valid while invoking them.
+    ///
+    /// Callbacks are also invoked when the context is dropped without calling `shutdown`.
+    ///
+    /// # Return Value
+    ///
+    /// The identifier of the callback, which can be passed to `remove_on_shutdown`.
+    pub fn add_on_shutdown(&self, callback: ShutdownCallback) -> u64 {
+        let mut guard = self.on_shutdown.lock();
+        let id = guard.next_id;
+        guard.next_id += 1;
+        guard.callbacks.insert(id, callback);
+        id
+    }
+
+    /// Unregister a callback registered by `add_on_shutdown`.
+    /// This returns `false` if there is no such callback.
+    pub fn remove_on_shutdown(&self, id: u64) -> bool {
+        self.on_shutdown.lock().callbacks.remove(&id).is_some()
+    }
+
+    /// Shut down the context.
+    ///
+    /// This invokes callbacks registered by `add_on_shutdown`, and then shuts down the context.
+    /// Blocking `Selector::wait` and pending asynchronous receivers of the context
+    /// are woken up and return `ContextShutdown` as an error.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::AlreadyShutdown` if the context has already been shut down, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn shutdown(&self) -> RCLResult<()> {
+        if self
+            .is_shutdown
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
         {
-            let mut guard = CONTEXT.lock();
-            *guard = Some(context.clone());
+            return Err(RCLError::AlreadyShutdown);
         }
 
-        Ok(context)
+        self.invoke_on_shutdown();
+        let result = rcl::MTSafeFn::rcl_shutdown(unsafe { self.as_ptr_mut() });
+
+        // wake up selectors waiting events of this context,
+        // even if rcl_shutdown failed because the context is already regarded as shut down
+        let conds = std::mem::take(&mut *self.wakeup_conds.lock());
+        for (_, cond) in conds {
+            if let Some(cond) = cond.upgrade() {
+                let guard = rcl::MT_UNSAFE_FN.lock();
+                let _ = guard.rcl_trigger_guard_condition(unsafe { cond.as_ptr_mut() });
+            }
+        }
+
+        // stop the selector for asynchronous receivers
+        let _ = SELECTOR.lock().halt_context(self.as_ptr());
+
+        result
+    }
+
+    fn invoke_on_shutdown(&self) {
+        let callbacks = std::mem::take(&mut self.on_shutdown.lock().callbacks);
+        for (_, callback) in callbacks {
+            callback();
+        }
+    }
+
+    /// Register a guard condition triggered when the context is shut down.
+    pub(crate) fn register_wakeup_cond(&self, cond: &Arc<RCLGuardCondition>) {
+        self.wakeup_conds
+            .lock()
+            .insert(cond.as_ptr(), Arc::downgrade(cond));
+    }
+
+    pub(crate) fn unregister_wakeup_cond(&self, cond: &Arc<RCLGuardCondition>) {
+        self.wakeup_conds.lock().remove(&cond.as_ptr());
+    }
+
+    /// Get the domain ID of the context.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid.
+    pub fn get_domain_id(&self) -> RCLResult<usize> {
+        let mut domain_id = 0;
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        guard.rcl_context_get_domain_id(unsafe { self.as_ptr_mut() }, &mut domain_id)?;
+        Ok(domain_id)
     }
 
     /// Create a new node of ROS2.
@@ -163,7 +348,11 @@ impl Context {
 
 impl Drop for Context {
     fn drop(&mut self) {
-        rcl::MTSafeFn::rcl_shutdown(&mut self.context).unwrap();
+        if !self.is_shutdown.swap(true, Ordering::AcqRel) {
+            self.invoke_on_shutdown();
+            rcl::MTSafeFn::rcl_shutdown(&mut self.context).unwrap();
+        }
+
         {
             let guard = rcl::MT_UNSAFE_FN.lock();
             guard.rcl_context_fini(&mut self.context).unwrap();
@@ -171,6 +360,95 @@ impl Drop for Context {
     }
 }
 
+/// Builder of contexts.
+///
+/// A context created by `ContextBuilder::build` has its own arguments and options,
+/// and it is independent of the global context returned by `Context::new`.
+/// So, several contexts, even on different domains, can coexist in a process.
+/// Each context has its own nodes and selectors.
+///
+/// # Example
+///
+/// ```
+/// use safe_drive::context::ContextBuilder;
+///
+/// // Create two independent contexts.
+/// let ctx1 = ContextBuilder::new()
+///     .args(["my_program", "--ros-args", "-r", "__ns:=/ctx1"])
+///     .domain_id(10)
+///     .build()
+///     .unwrap();
+///
+/// let ctx2 = ContextBuilder::new().domain_id(11).build().unwrap();
+///
+/// let node1 = ctx1.create_node("context_builder_rs", None, Default::default()).unwrap();
+/// let node2 = ctx2.create_node("context_builder_rs", None, Default::default()).unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ContextBuilder {
+    args: Option<Vec<String>>,
+    domain_id: Option<usize>,
+    localhost_only: Option<bool>,
+}
+
+impl ContextBuilder {
+    /// Create a builder.
+    /// If nothing is specified, the context is initialized by the same
+    /// arguments and options as `Context::new`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Specify arguments instead of `std::env::args()`.
+    /// Like `std::env::args()`, the first element is the name of the program.
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args = Some(args.into_iter().map(|s| s.into()).collect());
+        self
+    }
+
+    /// Specify the domain ID.
+    /// If this is not specified, `ROS_DOMAIN_ID` environment variable or the default domain ID is used.
+    pub fn domain_id(mut self, domain_id: usize) -> Self {
+        self.domain_id = Some(domain_id);
+        self
+    }
+
+    /// Specify whether communication is restricted to localhost.
+    /// If this is not specified, `ROS_LOCALHOST_ONLY` environment variable is used.
+    pub fn localhost_only(mut self, localhost_only: bool) -> Self {
+        self.localhost_only = Some(localhost_only);
+        self
+    }
+
+    /// Create a new independent context.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::InvalidRosArgs` if the ROS specific arguments are invalid, or
+    /// - `RCLError::BadAlloc` if allocating memory failed, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn build(self) -> Result<Arc<Context>, DynError> {
+        signal_handler::init();
+
+        let args = self.args.unwrap_or_else(|| env::args().collect());
+
+        let mut options = InitOptions::new()?;
+        if let Some(domain_id) = self.domain_id {
+            options.set_domain_id(domain_id)?;
+        }
+        if let Some(localhost_only) = self.localhost_only {
+            options.set_localhost_only(localhost_only);
+        }
+
+        Ok(Arc::new(Context::init(&args, &options)?))
+    }
+}
+
 /// Options for the initialization of the context.
 pub(crate) struct InitOptions {
     options: rcl::rcl_init_options_t,
@@ -191,6 +469,33 @@ impl InitOptions {
         Ok(InitOptions { options })
     }
 
+    pub fn set_domain_id(&mut self, domain_id: usize) -> RCLResult<()> {
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        guard.rcl_init_options_set_domain_id(self.as_ptr_mut(), domain_id)
+    }
+
+    pub fn set_localhost_only(&mut self, localhost_only: bool) {
+        #[cfg(feature = "galactic")]
+        let value = if localhost_only {
+            rcl::rmw_localhost_only_t_RMW_LOCALHOST_ONLY_ENABLED
+        } else {
+            rcl::rmw_localhost_only_t_RMW_LOCALHOST_ONLY_DISABLED
+        };
+
+        #[cfg(any(feature = "humble", feature = "iron"))]
+        let value = if localhost_only {
+            rcl::rmw_localhost_only_e_RMW_LOCALHOST_ONLY_ENABLED
+        } else {
+            rcl::rmw_localhost_only_e_RMW_LOCALHOST_ONLY_DISABLED
+        };
+
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        let rmw_options = guard.rcl_init_options_get_rmw_init_options(self.as_ptr_mut());
+        if let Some(rmw_options) = unsafe { rmw_options.as_mut() } {
+            rmw_options.localhost_only = value;
+        }
+    }
+
     pub fn as_ptr(&self) -> *const rcl::rcl_init_options_t {
         &self.options
     }
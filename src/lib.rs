@@ -30,13 +30,19 @@
 //!
 //! - Context
 //!   - [`context::Context`]
+//!   - [`context::ContextBuilder`]
 //! - Node
 //!   - [`node::Node`]
+//!   - [`node::NodeOptionsBuilder`]
 //! - Selector
 //!   - [`selector::Selector`]
 //! - Topic
 //!   - [`topic::publisher::Publisher`]
 //!   - [`topic::subscriber::Subscriber`]
+//!   - [`topic::generic::GenericPublisher`]
+//!   - [`topic::generic::GenericSubscriber`]
+//!   - [`topic::serialized::SerializedMessage`]
+//!   - [`topic::event::Event`]
 //! - Service
 //!   - [`service::client::Client`]
 //!   - [`service::client::ClientRecv`]
@@ -236,6 +242,7 @@ pub mod error;
 pub mod helper;
 pub mod logger;
 pub mod msg;
+pub mod name;
 pub mod node;
 pub mod parameter;
 pub mod publisher_loaned_message;
@@ -0,0 +1,234 @@
+//! Validation of topic and service names.
+//!
+//! Names are validated by the same rules of `rcl_validate_topic_name`,
+//! but `InvalidNameError` tells the offending character and its position.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::name::{validate_topic_name, InvalidNameReason};
+//!
+//! assert!(validate_topic_name("~/scan").is_ok());
+//!
+//! let err = validate_topic_name("/front/2d-scan").unwrap_err();
+//! assert_eq!(err.index, 7);
+//! assert_eq!(err.reason, InvalidNameReason::TokenStartsWithNumber);
+//! ```
+
+use std::{error::Error, fmt::Display};
+
+/// Reason why a name is invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidNameReason {
+    /// The name is empty.
+    Empty,
+
+    /// The name ends with `/`.
+    EndsWithForwardSlash,
+
+    /// The name contains a character other than alphanumerics, `_`, `/`, `~`, `{`, and `}`.
+    UnallowedCharacter(char),
+
+    /// A substitution contains a character other than alphanumerics and `_`.
+    SubstitutionContainsUnallowedCharacter(char),
+
+    /// The name or a token after `/` starts with a number.
+    TokenStartsWithNumber,
+
+    /// The name contains `//`.
+    RepeatedForwardSlash,
+
+    /// `{` or `}` is not matched.
+    UnmatchedCurlyBrace,
+
+    /// `~` appears other than the beginning.
+    MisplacedTilde,
+
+    /// `~` is not followed by `/`.
+    TildeNotFollowedByForwardSlash,
+}
+
+impl Display for InvalidNameReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "must not be empty"),
+            Self::EndsWithForwardSlash => write!(f, "must not end with '/'"),
+            Self::UnallowedCharacter(c) => write!(f, "contains an unallowed character '{c}'"),
+            Self::SubstitutionContainsUnallowedCharacter(c) => {
+                write!(f, "substitution contains an unallowed character '{c}'")
+            }
+            Self::TokenStartsWithNumber => write!(f, "token must not start with a number"),
+            Self::RepeatedForwardSlash => write!(f, "must not contain repeated '/'"),
+            Self::UnmatchedCurlyBrace => write!(f, "contains an unmatched curly brace"),
+            Self::MisplacedTilde => write!(f, "'~' is allowed only at the beginning"),
+            Self::TildeNotFollowedByForwardSlash => write!(f, "'~' must be followed by '/'"),
+        }
+    }
+}
+
+/// Error returned when a topic or service name is invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidNameError {
+    /// The invalid name.
+    pub name: String,
+
+    /// The position of the offending character in bytes.
+    pub index: usize,
+
+    /// Why the name is invalid.
+    pub reason: InvalidNameReason,
+}
+
+impl Display for InvalidNameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid name \"{}\": {} (at index {})",
+            self.name, self.reason, self.index
+        )
+    }
+}
+
+impl Error for InvalidNameError {}
+
+/// Validate a topic or service name before expansion.
+/// Relative names, `~/` and substitutions such as `{node}` are allowed.
+pub fn validate_topic_name(name: &str) -> Result<(), InvalidNameError> {
+    let err = |index, reason| {
+        Err(InvalidNameError {
+            name: name.to_string(),
+            index,
+            reason,
+        })
+    };
+
+    if name.is_empty() {
+        return err(0, InvalidNameReason::Empty);
+    }
+
+    let mut substitution_start = None;
+    let mut prev = None;
+
+    for (i, c) in name.char_indices() {
+        if let Some(start) = substitution_start {
+            // inside of a substitution
+            match c {
+                '}' => substitution_start = None,
+                '_' => (),
+                c if c.is_ascii_alphanumeric() => {
+                    if c.is_ascii_digit() && i == start + 1 {
+                        return err(i, InvalidNameReason::TokenStartsWithNumber);
+                    }
+                }
+                c => {
+                    return err(
+                        i,
+                        InvalidNameReason::SubstitutionContainsUnallowedCharacter(c),
+                    )
+                }
+            }
+        } else {
+            match c {
+                '{' => substitution_start = Some(i),
+                '}' => return err(i, InvalidNameReason::UnmatchedCurlyBrace),
+                '~' => {
+                    if i != 0 {
+                        return err(i, InvalidNameReason::MisplacedTilde);
+                    }
+                }
+                '/' => {
+                    if prev == Some('/') {
+                        return err(i, InvalidNameReason::RepeatedForwardSlash);
+                    }
+                }
+                '_' => (),
+                c if c.is_ascii_alphanumeric() => {
+                    if c.is_ascii_digit() && matches!(prev, None | Some('/')) {
+                        return err(i, InvalidNameReason::TokenStartsWithNumber);
+                    }
+                }
+                c => return err(i, InvalidNameReason::UnallowedCharacter(c)),
+            }
+        }
+
+        if prev == Some('~') && c != '/' {
+            return err(i, InvalidNameReason::TildeNotFollowedByForwardSlash);
+        }
+
+        prev = Some(c);
+    }
+
+    if let Some(start) = substitution_start {
+        return err(start, InvalidNameReason::UnmatchedCurlyBrace);
+    }
+
+    if name.ends_with('/') {
+        return err(name.len() - 1, InvalidNameReason::EndsWithForwardSlash);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_topic_name, InvalidNameReason};
+
+    fn reason(name: &str) -> (usize, InvalidNameReason) {
+        let err = validate_topic_name(name).unwrap_err();
+        (err.index, err.reason)
+    }
+
+    #[test]
+    fn test_valid_names() {
+        for name in [
+            "chatter",
+            "/chatter",
+            "~",
+            "~/chatter",
+            "ns/chatter_2",
+            "{node}/data",
+            "/a/b/{ns}",
+        ] {
+            assert!(validate_topic_name(name).is_ok(), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_names() {
+        assert_eq!(reason(""), (0, InvalidNameReason::Empty));
+        assert_eq!(
+            reason("/chatter/"),
+            (8, InvalidNameReason::EndsWithForwardSlash)
+        );
+        assert_eq!(
+            reason("chat-ter"),
+            (4, InvalidNameReason::UnallowedCharacter('-'))
+        );
+        assert_eq!(
+            reason("2chatter"),
+            (0, InvalidNameReason::TokenStartsWithNumber)
+        );
+        assert_eq!(
+            reason("/ns/2d"),
+            (4, InvalidNameReason::TokenStartsWithNumber)
+        );
+        assert_eq!(
+            reason("/ns//chatter"),
+            (4, InvalidNameReason::RepeatedForwardSlash)
+        );
+        assert_eq!(reason("/ns/~"), (4, InvalidNameReason::MisplacedTilde));
+        assert_eq!(
+            reason("~chatter"),
+            (1, InvalidNameReason::TildeNotFollowedByForwardSlash)
+        );
+        assert_eq!(reason("{node"), (0, InvalidNameReason::UnmatchedCurlyBrace));
+        assert_eq!(reason("node}"), (4, InvalidNameReason::UnmatchedCurlyBrace));
+        assert_eq!(
+            reason("{no-de}"),
+            (
+                3,
+                InvalidNameReason::SubstitutionContainsUnallowedCharacter('-')
+            )
+        );
+    }
+}
@@ -14,21 +14,58 @@
 //!     .create_node("node_rs", Some("namespace"), Default::default())
 //!     .unwrap();
 //! ```
+//!
+//! # Options
+//!
+//! Node-local arguments, such as remapping rules and parameter overrides,
+//! can be specified by `NodeOptionsBuilder`.
+//!
+//! ```
+//! use safe_drive::{context::Context, node::NodeOptions};
+//!
+//! let ctx = Context::new().unwrap();
+//!
+//! // Remap "chatter" to "chatter_remapped", and disable rosout.
+//! let options = NodeOptions::builder()
+//!     .remap("chatter", "chatter_remapped")
+//!     .parameter("rate", "10")
+//!     .use_global_arguments(false)
+//!     .enable_rosout(false)
+//!     .build()
+//!     .unwrap();
+//!
+//! let node = ctx
+//!     .create_node("node_options_rs", None, options)
+//!     .unwrap();
+//! ```
 
 use libc::atexit;
 
 use crate::{
     context::{remove_context, Context},
-    error::{DynError, RCLResult},
+    error::{DynError, RCLError, RCLResult},
+    get_allocator,
     helper::InitOnce,
     msg::{ServiceMsg, TypeSupport},
-    parameter::ParameterServer,
-    qos, rcl,
+    name::validate_topic_name,
+    parameter::{collect_overrides, ParameterServer, Parameters, Value},
+    qos::{self, overriding::QoSOverridingOptions},
+    rcl,
     service::{client::Client, server::Server},
+    topic::generic::{GenericPublisher, GenericSubscriber},
     topic::publisher::Publisher,
     topic::subscriber::Subscriber,
 };
-use std::{ffi::CString, sync::Arc};
+use parking_lot::{Mutex, RwLock};
+use std::{
+    collections::BTreeMap,
+    ffi::{CStr, CString},
+    os::raw::c_char,
+    ptr::null_mut,
+    sync::{Arc, Weak},
+};
+
+pub mod graph;
 
 static SET_ATEXIT: InitOnce = InitOnce::new();
 
@@ -38,6 +75,7 @@ pub struct Node {
     name: String,
     namespace: Option<String>,
     init_param_server: InitOnce,
+    params: Mutex<Weak<RwLock<Parameters>>>,
     pub(crate) context: Arc<Context>,
 }
 
@@ -74,6 +112,7 @@ impl Node {
             name: name.to_string(),
             namespace: namespace.map_or_else(|| None, |v| Some(v.to_string())),
             init_param_server: InitOnce::new(),
+            params: Mutex::new(Weak::new()),
             context,
         }))
     }
@@ -94,11 +133,136 @@ impl Node {
         &self.namespace
     }
 
+    /// Get the fully qualified name of the node, such as `/namespace/node_name`.
+    /// Remapping rules of the node name and namespace are applied.
+    pub fn get_fully_qualified_name(&self) -> String {
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        let name = guard.rcl_node_get_fully_qualified_name(&self.node);
+        unsafe { CStr::from_ptr(name) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Resolve a topic name as the node does when creating publishers and subscribers.
+    /// `~/` is expanded to the fully qualified name of the node,
+    /// a relative name is prefixed by the namespace,
+    /// and then remapping rules are applied.
+    ///
+    /// # Errors
+    ///
+    /// - `name::InvalidNameError` if `topic_name` is invalid, which tells the offending character, or
+    /// - `RCLError` if rcl failed to resolve the name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::context::Context;
+    ///
+    /// let ctx = Context::new().unwrap();
+    /// let node = ctx
+    ///     .create_node("resolve_rs", Some("ns"), Default::default())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(node.resolve_topic_name("~/scan").unwrap(), "/ns/resolve_rs/scan");
+    /// assert_eq!(node.resolve_topic_name("scan").unwrap(), "/ns/scan");
+    /// assert!(node.resolve_topic_name("2d-scan").is_err());
+    /// ```
+    pub fn resolve_topic_name(&self, topic_name: &str) -> Result<String, DynError> {
+        self.resolve_name(topic_name, false, false)
+    }
+
+    /// Resolve a service name as the node does when creating servers and clients.
+    /// See `Node::resolve_topic_name`.
+    pub fn resolve_service_name(&self, service_name: &str) -> Result<String, DynError> {
+        self.resolve_name(service_name, true, false)
+    }
+
+    pub(crate) fn resolve_name(
+        &self,
+        name: &str,
+        is_service: bool,
+        only_expand: bool,
+    ) -> Result<String, DynError> {
+        validate_topic_name(name)?;
+
+        let name_c = CString::new(name)?;
+        let allocator = get_allocator();
+        let mut output: *mut c_char = null_mut();
+
+        {
+            let guard = rcl::MT_UNSAFE_FN.lock();
+            guard.rcl_node_resolve_name(
+                &self.node,
+                name_c.as_ptr(),
+                allocator,
+                is_service,
+                only_expand,
+                &mut output,
+            )?;
+        }
+
+        let resolved = unsafe { CStr::from_ptr(output) }
+            .to_string_lossy()
+            .into_owned();
+
+        if let Some(deallocate) = allocator.deallocate {
+            unsafe { deallocate(output as *mut _, allocator.state) };
+        }
+
+        Ok(resolved)
+    }
+
     pub fn create_parameter_server(self: &Arc<Self>) -> Result<ParameterServer, DynError> {
-        self.init_param_server.init(
+        let param_server = self.init_param_server.init(
             || ParameterServer::new(self.clone()),
             Err("a parameter server has been already created".into()),
-        )
+        )?;
+
+        *self.params.lock() = Arc::downgrade(&param_server.params);
+        Ok(param_server)
+    }
+
+    /// Get parameter overrides of the node given by command line arguments,
+    /// parameter files, or `NodeOptionsBuilder::parameter`.
+    /// Node-local overrides take precedence over global ones.
+    ///
+    /// Parameters of `ParameterServer` are not included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{context::Context, node::NodeOptions, parameter::Value};
+    ///
+    /// let ctx = Context::new().unwrap();
+    /// let options = NodeOptions::builder()
+    ///     .parameter("rate", "10")
+    ///     .build()
+    ///     .unwrap();
+    /// let node = ctx
+    ///     .create_node("parameter_overrides_rs", None, options)
+    ///     .unwrap();
+    ///
+    /// let overrides = node.get_parameter_overrides().unwrap();
+    /// assert!(matches!(overrides.get("rate"), Some(Value::I64(10))));
+    /// ```
+    pub fn get_parameter_overrides(&self) -> RCLResult<BTreeMap<String, Value>> {
+        let fqn = self.get_fully_qualified_name();
+        let mut overrides = BTreeMap::new();
+
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        let options = guard.rcl_node_get_options(&self.node);
+        if options.is_null() {
+            return Err(RCLError::NodeInvalid);
+        }
+
+        let options = unsafe { &*options };
+        if options.use_global_arguments {
+            let global_arguments = unsafe { &(*self.context.as_ptr()).global_arguments };
+            get_overrides(&guard, global_arguments, &fqn, &mut overrides)?;
+        }
+        get_overrides(&guard, &options.arguments, &fqn, &mut overrides)?;
+
+        Ok(overrides)
     }
 
     /// Create a publisher.
@@ -125,6 +289,13 @@ impl Node {
     ///     }
     /// }
     /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::TopicNameInvalid` if `topic_name` is invalid, or
+    /// - `RCLError` if creating the publisher failed.
+    ///
+    /// `Node::resolve_topic_name` tells the offending character of an invalid name.
     pub fn create_publisher<T: TypeSupport>(
         self: &Arc<Self>,
         topic_name: &str,
@@ -168,6 +339,13 @@ impl Node {
     ///     }
     /// }
     /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::TopicNameInvalid` if `topic_name` is invalid, or
+    /// - `RCLError` if creating the subscriber failed.
+    ///
+    /// `Node::resolve_topic_name` tells the offending character of an invalid name.
     pub fn create_subscriber<T: TypeSupport>(
         self: &Arc<Self>,
         topic_name: &str,
@@ -186,6 +364,414 @@ impl Node {
         }
     }
 
+    /// Create a publisher which hands messages over to intra-process subscribers
+    /// of the same context without the middleware.
+    /// If `qos` is specified `None`,
+    /// the default profile is used.
+    ///
+    /// See `topic::intra_process`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{msg::common_interfaces::std_msgs, node::Node, topic::publisher::Publisher};
+    /// use std::sync::Arc;
+    ///
+    /// fn create_new_intra_process_publisher(node: Arc<Node>) -> Publisher<std_msgs::msg::Bool> {
+    ///     #[cfg(any(feature = "humble", feature = "galactic"))]
+    ///     {
+    ///         node.create_intra_process_publisher("topic_name", None).unwrap()
+    ///     }
+    ///
+    ///     #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    ///     {
+    ///         node.create_intra_process_publisher("topic_name", None, true).unwrap()
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::TopicNameInvalid` if `topic_name` is invalid, or
+    /// - `RCLError` if creating the publisher failed.
+    ///
+    /// `Node::resolve_topic_name` tells the offending character of an invalid name.
+    pub fn create_intra_process_publisher<T: TypeSupport>(
+        self: &Arc<Self>,
+        topic_name: &str,
+        qos: Option<qos::Profile>,
+
+        #[cfg(not(any(feature = "humble", feature = "galactic")))] disable_loaned_massage: bool,
+    ) -> RCLResult<Publisher<T>> {
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        {
+            Publisher::new_intra_process(self.clone(), topic_name, qos, disable_loaned_massage)
+        }
+
+        #[cfg(any(feature = "humble", feature = "galactic"))]
+        {
+            Publisher::new_intra_process(self.clone(), topic_name, qos)
+        }
+    }
+
+    /// Create a subscriber which receives messages from intra-process publishers
+    /// of the same context without the middleware.
+    /// If `qos` is specified `None`,
+    /// the default profile is used.
+    ///
+    /// Messages sent through the middleware by other publishers of the same context are ignored.
+    /// See `topic::intra_process`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{msg::common_interfaces::std_msgs, node::Node, topic::subscriber::Subscriber};
+    /// use std::sync::Arc;
+    ///
+    /// fn create_new_intra_process_subscriber(node: Arc<Node>) -> Subscriber<std_msgs::msg::Bool> {
+    ///     #[cfg(any(feature = "humble", feature = "galactic"))]
+    ///     {
+    ///         node.create_intra_process_subscriber("topic_name", None).unwrap()
+    ///     }
+    ///
+    ///     #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    ///     {
+    ///         node.create_intra_process_subscriber("topic_name", None, true).unwrap()
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::TopicNameInvalid` if `topic_name` is invalid, or
+    /// - `RCLError` if creating the subscriber failed.
+    ///
+    /// `Node::resolve_topic_name` tells the offending character of an invalid name.
+    pub fn create_intra_process_subscriber<T: TypeSupport>(
+        self: &Arc<Self>,
+        topic_name: &str,
+        qos: Option<qos::Profile>,
+
+        #[cfg(not(any(feature = "humble", feature = "galactic")))] disable_loaned_massage: bool,
+    ) -> RCLResult<Subscriber<T>> {
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        {
+            Subscriber::new_intra_process(self.clone(), topic_name, qos, disable_loaned_massage)
+        }
+
+        #[cfg(any(feature = "humble", feature = "galactic"))]
+        {
+            Subscriber::new_intra_process(self.clone(), topic_name, qos)
+        }
+    }
+
+    /// Create a publisher whose QoS policies can be overridden by parameters,
+    /// such as `qos_overrides./topic_name.publisher.reliability`.
+    /// If `qos` is specified `None`,
+    /// the default profile is used before overriding.
+    ///
+    /// See `qos::overriding`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{
+    ///     msg::common_interfaces::std_msgs, node::Node, qos::overriding::QoSOverridingOptions,
+    ///     topic::publisher::Publisher,
+    /// };
+    /// use std::sync::Arc;
+    ///
+    /// fn create_overridable_publisher(node: Arc<Node>) -> Publisher<std_msgs::msg::Bool> {
+    ///     let options = QoSOverridingOptions::with_default_policies();
+    ///
+    ///     #[cfg(any(feature = "humble", feature = "galactic"))]
+    ///     {
+    ///         node.create_publisher_with_qos_overrides("topic_name", None, &options)
+    ///             .unwrap()
+    ///     }
+    ///
+    ///     #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    ///     {
+    ///         node.create_publisher_with_qos_overrides("topic_name", None, &options, true)
+    ///             .unwrap()
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - an error if an overriding parameter has an invalid value, or
+    /// - errors of `create_publisher`.
+    pub fn create_publisher_with_qos_overrides<T: TypeSupport>(
+        self: &Arc<Self>,
+        topic_name: &str,
+        qos: Option<qos::Profile>,
+        options: &QoSOverridingOptions,
+
+        #[cfg(not(any(feature = "humble", feature = "galactic")))] disable_loaned_massage: bool,
+    ) -> Result<Publisher<T>, DynError> {
+        let qos = self.override_qos(topic_name, qos, options, "publisher")?;
+
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        {
+            Ok(Publisher::new(
+                self.clone(),
+                topic_name,
+                Some(qos),
+                disable_loaned_massage,
+            )?)
+        }
+
+        #[cfg(any(feature = "humble", feature = "galactic"))]
+        {
+            Ok(Publisher::new(self.clone(), topic_name, Some(qos))?)
+        }
+    }
+
+    /// Create a subscriber whose QoS policies can be overridden by parameters,
+    /// such as `qos_overrides./topic_name.subscription.reliability`.
+    /// If `qos` is specified `None`,
+    /// the default profile is used before overriding.
+    ///
+    /// See `qos::overriding`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{
+    ///     msg::common_interfaces::std_msgs, node::Node, qos::overriding::QoSOverridingOptions,
+    ///     topic::subscriber::Subscriber,
+    /// };
+    /// use std::sync::Arc;
+    ///
+    /// fn create_overridable_subscriber(node: Arc<Node>) -> Subscriber<std_msgs::msg::Bool> {
+    ///     let options = QoSOverridingOptions::with_default_policies();
+    ///
+    ///     #[cfg(any(feature = "humble", feature = "galactic"))]
+    ///     {
+    ///         node.create_subscriber_with_qos_overrides("topic_name", None, &options)
+    ///             .unwrap()
+    ///     }
+    ///
+    ///     #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    ///     {
+    ///         node.create_subscriber_with_qos_overrides("topic_name", None, &options, true)
+    ///             .unwrap()
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - an error if an overriding parameter has an invalid value, or
+    /// - errors of `create_subscriber`.
+    pub fn create_subscriber_with_qos_overrides<T: TypeSupport>(
+        self: &Arc<Self>,
+        topic_name: &str,
+        qos: Option<qos::Profile>,
+        options: &QoSOverridingOptions,
+
+        #[cfg(not(any(feature = "humble", feature = "galactic")))] disable_loaned_massage: bool,
+    ) -> Result<Subscriber<T>, DynError> {
+        let qos = self.override_qos(topic_name, qos, options, "subscription")?;
+
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        {
+            Ok(Subscriber::new(
+                self.clone(),
+                topic_name,
+                Some(qos),
+                disable_loaned_massage,
+            )?)
+        }
+
+        #[cfg(any(feature = "humble", feature = "galactic"))]
+        {
+            Ok(Subscriber::new(self.clone(), topic_name, Some(qos))?)
+        }
+    }
+
+    fn override_qos(
+        &self,
+        topic_name: &str,
+        qos: Option<qos::Profile>,
+        options: &QoSOverridingOptions,
+        entity: &str,
+    ) -> Result<qos::Profile, DynError> {
+        let resolved = self.resolve_topic_name(topic_name)?;
+        let overrides = self.get_parameter_overrides()?;
+
+        // Parameters of the parameter server take precedence over the overrides.
+        let params = self.params.lock().upgrade();
+        let mut guard = params.as_ref().map(|params| params.write());
+        options.apply(
+            qos.unwrap_or_default(),
+            &resolved,
+            entity,
+            &overrides,
+            guard.as_deref_mut(),
+        )
+    }
+
+    /// Create a subscriber whose messages are filtered by the middleware.
+    /// If `qos` is specified `None`,
+    /// the default profile is used.
+    ///
+    /// `expression` is similar to the WHERE part of an SQL clause,
+    /// and `%n` in `expression` is replaced by `parameters[n]`.
+    /// See `Subscriber::set_content_filter`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{msg::common_interfaces::std_msgs, node::Node, topic::subscriber::Subscriber};
+    /// use std::sync::Arc;
+    ///
+    /// #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    /// fn create_filtered_subscriber(node: Arc<Node>) -> Subscriber<std_msgs::msg::UInt32> {
+    ///     node.create_subscriber_with_filter("topic_name", None, "data > %0", &["100"], true)
+    ///         .unwrap()
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::Unsupported` if the middleware does not support content filtered topics, or
+    /// - errors of `create_subscriber`.
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub fn create_subscriber_with_filter<T: TypeSupport>(
+        self: &Arc<Self>,
+        topic_name: &str,
+        qos: Option<qos::Profile>,
+        expression: &str,
+        parameters: &[&str],
+        disable_loaned_massage: bool,
+    ) -> RCLResult<Subscriber<T>> {
+        Subscriber::new_with_filter(
+            self.clone(),
+            topic_name,
+            qos,
+            expression,
+            parameters,
+            disable_loaned_massage,
+        )
+    }
+
+    /// Create a publisher whose message type is specified at runtime.
+    /// If `qos` is specified `None`,
+    /// the default profile is used.
+    ///
+    /// `type_name` is the type of messages, such as `"std_msgs/msg/UInt32"`,
+    /// and the type support of the type is loaded dynamically.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{node::Node, topic::generic::GenericPublisher};
+    /// use std::sync::Arc;
+    ///
+    /// fn create_new_generic_publisher(node: Arc<Node>) -> GenericPublisher {
+    ///     #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    ///     {
+    ///         node.create_generic_publisher("topic_name", "std_msgs/msg/Bool", None, true)
+    ///             .unwrap()
+    ///     }
+    ///
+    ///     #[cfg(any(feature = "humble", feature = "galactic"))]
+    ///     {
+    ///         node.create_generic_publisher("topic_name", "std_msgs/msg/Bool", None)
+    ///             .unwrap()
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `TypeSupportError` if the type support of `type_name` cannot be loaded, or
+    /// - `RCLError` if creating the publisher failed.
+    pub fn create_generic_publisher(
+        self: &Arc<Self>,
+        topic_name: &str,
+        type_name: &str,
+        qos: Option<qos::Profile>,
+
+        #[cfg(all(not(feature = "humble"), not(feature = "galactic")))]
+        disable_loaned_massage: bool,
+    ) -> Result<GenericPublisher, DynError> {
+        #[cfg(all(not(feature = "humble"), not(feature = "galactic")))]
+        {
+            GenericPublisher::new(
+                self.clone(),
+                topic_name,
+                type_name,
+                qos,
+                disable_loaned_massage,
+            )
+        }
+
+        #[cfg(any(feature = "humble", feature = "galactic"))]
+        {
+            GenericPublisher::new(self.clone(), topic_name, type_name, qos)
+        }
+    }
+
+    /// Create a subscriber whose message type is specified at runtime.
+    /// If `qos` is specified `None`,
+    /// the default profile is used.
+    ///
+    /// `type_name` is the type of messages, such as `"std_msgs/msg/UInt32"`,
+    /// and the type support of the type is loaded dynamically.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{node::Node, topic::generic::GenericSubscriber};
+    /// use std::sync::Arc;
+    ///
+    /// fn create_new_generic_subscriber(node: Arc<Node>) -> GenericSubscriber {
+    ///     #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    ///     {
+    ///         node.create_generic_subscriber("topic_name", "std_msgs/msg/Bool", None, true)
+    ///             .unwrap()
+    ///     }
+    ///
+    ///     #[cfg(any(feature = "humble", feature = "galactic"))]
+    ///     {
+    ///         node.create_generic_subscriber("topic_name", "std_msgs/msg/Bool", None)
+    ///             .unwrap()
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `TypeSupportError` if the type support of `type_name` cannot be loaded, or
+    /// - `RCLError` if creating the subscriber failed.
+    pub fn create_generic_subscriber(
+        self: &Arc<Self>,
+        topic_name: &str,
+        type_name: &str,
+        qos: Option<qos::Profile>,
+
+        #[cfg(not(any(feature = "humble", feature = "galactic")))] disable_loaned_massage: bool,
+    ) -> Result<GenericSubscriber, DynError> {
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        {
+            GenericSubscriber::new(
+                self.clone(),
+                topic_name,
+                type_name,
+                qos,
+                disable_loaned_massage,
+            )
+        }
+
+        #[cfg(any(feature = "humble", feature = "galactic"))]
+        {
+            GenericSubscriber::new(self.clone(), topic_name, type_name, qos)
+        }
+    }
+
     /// Create a server.
     /// If `qos` is specified `None`,
     /// the default profile is used.
@@ -202,6 +788,13 @@ impl Node {
     ///     node.create_server("service_name", None).unwrap()
     /// }
     /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::ServiceNameInvalid` if `service_name` is invalid, or
+    /// - `RCLError` if creating the server failed.
+    ///
+    /// `Node::resolve_service_name` tells the offending character of an invalid name.
     pub fn create_server<T: ServiceMsg>(
         self: &Arc<Self>,
         service_name: &str,
@@ -226,6 +819,13 @@ impl Node {
     ///     node.create_client("service_name", None).unwrap()
     /// }
     /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::ServiceNameInvalid` if `service_name` is invalid, or
+    /// - `RCLError` if creating the client failed.
+    ///
+    /// `Node::resolve_service_name` tells the offending character of an invalid name.
     pub fn create_client<T: ServiceMsg>(
         self: &Arc<Self>,
         service_name: &str,
@@ -255,12 +855,17 @@ impl Default for NodeOptions {
 }
 
 impl NodeOptions {
-    /// Create options to create a node
+    /// Create default options to create a node.
+    /// Use `NodeOptions::builder` to specify options.
     pub fn new() -> Self {
-        // TODO: allow setting options
         Default::default()
     }
 
+    /// Create a builder of options.
+    pub fn builder() -> NodeOptionsBuilder {
+        NodeOptionsBuilder::new()
+    }
+
     pub(crate) fn as_ptr(&self) -> *const rcl::rcl_node_options_t {
         &self.options
     }
@@ -273,5 +878,165 @@ impl Drop for NodeOptions {
     }
 }
 
+/// Allocator used for internal allocations of a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NodeAllocator {
+    /// The default allocator of rcutils.
+    #[default]
+    Default,
+
+    /// The allocator used by safe_drive,
+    /// which is `allocator::ALLOCATOR` if the `custom_alloc` feature is enabled.
+    SafeDrive,
+}
+
+/// Builder of `NodeOptions`.
+///
+/// # Example
+///
+/// ```
+/// use safe_drive::node::{NodeAllocator, NodeOptionsBuilder};
+///
+/// let options = NodeOptionsBuilder::new()
+///     .arguments(["--ros-args", "-r", "__node:=renamed_node"])
+///     .remap("/scan", "/front/scan")
+///     .allocator(NodeAllocator::SafeDrive)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct NodeOptionsBuilder {
+    arguments: Vec<String>,
+    use_global_arguments: bool,
+    enable_rosout: bool,
+    allocator: NodeAllocator,
+}
+
+impl Default for NodeOptionsBuilder {
+    fn default() -> Self {
+        NodeOptionsBuilder {
+            arguments: Vec::new(),
+            use_global_arguments: true,
+            enable_rosout: true,
+            allocator: NodeAllocator::Default,
+        }
+    }
+}
+
+impl NodeOptionsBuilder {
+    /// Create a builder with the default options,
+    /// which are the same as `NodeOptions::new()`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Append command line arguments applied only to the node.
+    /// ROS specific arguments must be scoped by `--ros-args`,
+    /// as command line arguments of a process.
+    pub fn arguments<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.arguments.extend(args.into_iter().map(|s| s.into()));
+        self
+    }
+
+    /// Append a remapping rule, which is equivalent to `--ros-args -r from:=to`.
+    /// `from` can be a topic name, a service name, `__node`, or `__ns`.
+    pub fn remap(self, from: &str, to: &str) -> Self {
+        self.arguments(["--ros-args", "-r", &format!("{from}:={to}")])
+    }
+
+    /// Append a parameter override, which is equivalent to `--ros-args -p name:=value`.
+    /// `value` is interpreted as YAML.
+    pub fn parameter(self, name: &str, value: &str) -> Self {
+        self.arguments(["--ros-args", "-p", &format!("{name}:={value}")])
+    }
+
+    /// If `false`, arguments of the context are not applied to the node.
+    /// Default is `true`.
+    pub fn use_global_arguments(mut self, use_global_arguments: bool) -> Self {
+        self.use_global_arguments = use_global_arguments;
+        self
+    }
+
+    /// If `false`, log messages of the node are not published to `/rosout`.
+    /// Default is `true`.
+    pub fn enable_rosout(mut self, enable_rosout: bool) -> Self {
+        self.enable_rosout = enable_rosout;
+        self
+    }
+
+    /// Set the allocator used for internal allocations of the node.
+    pub fn allocator(mut self, allocator: NodeAllocator) -> Self {
+        self.allocator = allocator;
+        self
+    }
+
+    /// Build `NodeOptions`.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if an argument contains a null character, or
+    /// - `RCLError::InvalidRosArgs` if an invalid or unknown ROS argument is found, or
+    /// - `RCLError::BadAlloc` if allocating memory failed, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn build(self) -> RCLResult<NodeOptions> {
+        let mut options = NodeOptions::default();
+
+        options.options.use_global_arguments = self.use_global_arguments;
+        options.options.enable_rosout = self.enable_rosout;
+        if let NodeAllocator::SafeDrive = self.allocator {
+            options.options.allocator = get_allocator();
+        }
+
+        if !self.arguments.is_empty() {
+            let args = self
+                .arguments
+                .into_iter()
+                .map(CString::new)
+                .collect::<Result<Vec<_>, _>>()
+                .or(Err(RCLError::InvalidArgument))?;
+            let argv: Vec<*const c_char> = args.iter().map(|arg| arg.as_ptr()).collect();
+
+            rcl::MTSafeFn::rcl_parse_arguments(
+                argv.len() as _,
+                argv.as_ptr(),
+                options.options.allocator,
+                &mut options.options.arguments,
+            )?;
+
+            if rcl::MTSafeFn::rcl_arguments_get_count_unparsed_ros(&options.options.arguments) > 0 {
+                return Err(RCLError::InvalidRosArgs);
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// Collect parameter overrides of `arguments` for the node whose fully qualified name is `node_fqn`.
+fn get_overrides(
+    guard: &rcl::MTUnsafeFn,
+    arguments: &rcl::rcl_arguments_t,
+    node_fqn: &str,
+    overrides: &mut BTreeMap<String, Value>,
+) -> RCLResult<()> {
+    if arguments.impl_.is_null() {
+        return Ok(());
+    }
+
+    let mut params: *mut rcl::rcl_params_t = null_mut();
+    guard.rcl_arguments_get_param_overrides(arguments, &mut params)?;
+
+    if !params.is_null() {
+        collect_overrides(unsafe { &*params }, node_fqn, overrides);
+        guard.rcl_yaml_node_struct_fini(params);
+    }
+
+    Ok(())
+}
+
 unsafe impl Sync for Node {}
 unsafe impl Send for Node {}
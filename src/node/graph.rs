@@ -0,0 +1,558 @@
+//! Introspection of the ROS graph.
+//!
+//! Nodes can discover topics, services, and other nodes through the ROS graph.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::context::Context;
+//!
+//! let ctx = Context::new().unwrap();
+//! let node = ctx
+//!     .create_node("graph_rs", None, Default::default())
+//!     .unwrap();
+//!
+//! // List all topics and their types.
+//! for (topic, types) in node.get_topic_names_and_types().unwrap() {
+//!     println!("{topic}: {types:?}");
+//! }
+//!
+//! // List all nodes.
+//! for (name, namespace) in node.get_node_names_and_namespaces().unwrap() {
+//!     println!("{namespace} {name}");
+//! }
+//!
+//! // Check QoS of publishers of a topic.
+//! for info in node.get_publishers_info_by_topic("chatter").unwrap() {
+//!     println!("{}: {:?}", info.node_name, info.qos.reliability);
+//! }
+//! ```
+
+use super::Node;
+use crate::{
+    context::{Context, ContextShutdown},
+    error::{DynError, RCLError, RCLResult},
+    get_allocator, is_halt, qos, rcl,
+    selector::{
+        async_selector::{Command, SELECTOR},
+        guard_condition::GuardCondition,
+        CallbackResult,
+    },
+    signal_handler::Signaled,
+};
+use parking_lot::Mutex;
+use std::{
+    collections::BTreeMap,
+    ffi::{CStr, CString},
+    future::Future,
+    os::raw::c_char,
+    pin::Pin,
+    sync::Arc,
+    task::{self, Poll, Waker},
+    time::{Duration, Instant},
+};
+
+/// Names of topics or services, and their types.
+/// A name may be associated with multiple types.
+pub type NamesAndTypes = BTreeMap<String, Vec<String>>;
+
+#[cfg(feature = "galactic")]
+use rcl::{
+    rmw_endpoint_type_t_RMW_ENDPOINT_PUBLISHER as RMW_ENDPOINT_PUBLISHER,
+    rmw_endpoint_type_t_RMW_ENDPOINT_SUBSCRIPTION as RMW_ENDPOINT_SUBSCRIPTION,
+};
+
+#[cfg(any(feature = "humble", feature = "iron"))]
+use rcl::{
+    rmw_endpoint_type_e_RMW_ENDPOINT_PUBLISHER as RMW_ENDPOINT_PUBLISHER,
+    rmw_endpoint_type_e_RMW_ENDPOINT_SUBSCRIPTION as RMW_ENDPOINT_SUBSCRIPTION,
+};
+
+/// Type of an endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointType {
+    Invalid,
+    Publisher,
+    Subscription,
+}
+
+/// Information of a publisher or a subscriber of a topic.
+#[derive(Debug, Clone)]
+pub struct TopicEndpointInfo {
+    pub node_name: String,
+    pub node_namespace: String,
+
+    /// Type name like `std_msgs/msg/String`.
+    pub topic_type: String,
+
+    pub endpoint_type: EndpointType,
+
+    /// Global identifier of the endpoint assigned by the middleware.
+    pub gid: Vec<u8>,
+
+    pub qos: qos::Profile,
+}
+
+impl From<&rcl::rmw_topic_endpoint_info_t> for TopicEndpointInfo {
+    fn from(info: &rcl::rmw_topic_endpoint_info_t) -> Self {
+        let endpoint_type = match info.endpoint_type {
+            RMW_ENDPOINT_PUBLISHER => EndpointType::Publisher,
+            RMW_ENDPOINT_SUBSCRIPTION => EndpointType::Subscription,
+            _ => EndpointType::Invalid,
+        };
+
+        unsafe {
+            TopicEndpointInfo {
+                node_name: cstr_to_string(info.node_name),
+                node_namespace: cstr_to_string(info.node_namespace),
+                topic_type: cstr_to_string(info.topic_type),
+                endpoint_type,
+                gid: info.endpoint_gid.to_vec(),
+                qos: (&info.qos_profile).into(),
+            }
+        }
+    }
+}
+
+impl Node {
+    /// Get names and types of all topics in the ROS graph.
+    /// Type names are like `std_msgs/msg/String`.
+    pub fn get_topic_names_and_types(&self) -> RCLResult<NamesAndTypes> {
+        self.get_names_and_types(|guard, allocator, names_and_types| {
+            guard.rcl_get_topic_names_and_types(&self.node, allocator, false, names_and_types)
+        })
+    }
+
+    /// Get names and types of all services in the ROS graph.
+    /// Type names are like `std_srvs/srv/Empty`.
+    pub fn get_service_names_and_types(&self) -> RCLResult<NamesAndTypes> {
+        self.get_names_and_types(|guard, allocator, names_and_types| {
+            guard.rcl_get_service_names_and_types(&self.node, allocator, names_and_types)
+        })
+    }
+
+    /// Get names and namespaces of all nodes in the ROS graph,
+    /// which are returned as `(name, namespace)`.
+    pub fn get_node_names_and_namespaces(&self) -> RCLResult<Vec<(String, String)>> {
+        let mut names = rcl::MTSafeFn::rcutils_get_zero_initialized_string_array();
+        let mut namespaces = rcl::MTSafeFn::rcutils_get_zero_initialized_string_array();
+
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        guard.rcl_get_node_names(&self.node, get_allocator(), &mut names, &mut namespaces)?;
+
+        let result = unsafe { string_array_to_vec(&names) }
+            .into_iter()
+            .zip(unsafe { string_array_to_vec(&namespaces) })
+            .collect();
+
+        guard.rcutils_string_array_fini(&mut names)?;
+        guard.rcutils_string_array_fini(&mut namespaces)?;
+
+        Ok(result)
+    }
+
+    /// Get names and types of topics published by a node.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::NodeNameNonExistent` if the node was not found, or
+    /// - `RCLError::NodeInvalidName` if `node_name` is invalid, or
+    /// - `RCLError::NodeInvalidNamespace` if `node_namespace` is invalid, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn get_publisher_names_and_types_by_node(
+        &self,
+        node_name: &str,
+        node_namespace: &str,
+    ) -> RCLResult<NamesAndTypes> {
+        let (name, namespace) = node_name_to_cstring(node_name, node_namespace)?;
+        self.get_names_and_types(|guard, allocator, names_and_types| {
+            guard.rcl_get_publisher_names_and_types_by_node(
+                &self.node,
+                allocator,
+                false,
+                name.as_ptr(),
+                namespace.as_ptr(),
+                names_and_types,
+            )
+        })
+    }
+
+    /// Get names and types of topics subscribed by a node.
+    /// See `Node::get_publisher_names_and_types_by_node` for errors.
+    pub fn get_subscriber_names_and_types_by_node(
+        &self,
+        node_name: &str,
+        node_namespace: &str,
+    ) -> RCLResult<NamesAndTypes> {
+        let (name, namespace) = node_name_to_cstring(node_name, node_namespace)?;
+        self.get_names_and_types(|guard, allocator, names_and_types| {
+            guard.rcl_get_subscriber_names_and_types_by_node(
+                &self.node,
+                allocator,
+                false,
+                name.as_ptr(),
+                namespace.as_ptr(),
+                names_and_types,
+            )
+        })
+    }
+
+    /// Get names and types of services served by a node.
+    /// See `Node::get_publisher_names_and_types_by_node` for errors.
+    pub fn get_service_names_and_types_by_node(
+        &self,
+        node_name: &str,
+        node_namespace: &str,
+    ) -> RCLResult<NamesAndTypes> {
+        let (name, namespace) = node_name_to_cstring(node_name, node_namespace)?;
+        self.get_names_and_types(|guard, allocator, names_and_types| {
+            guard.rcl_get_service_names_and_types_by_node(
+                &self.node,
+                allocator,
+                name.as_ptr(),
+                namespace.as_ptr(),
+                names_and_types,
+            )
+        })
+    }
+
+    /// Get names and types of services which a node has clients of.
+    /// See `Node::get_publisher_names_and_types_by_node` for errors.
+    pub fn get_client_names_and_types_by_node(
+        &self,
+        node_name: &str,
+        node_namespace: &str,
+    ) -> RCLResult<NamesAndTypes> {
+        let (name, namespace) = node_name_to_cstring(node_name, node_namespace)?;
+        self.get_names_and_types(|guard, allocator, names_and_types| {
+            guard.rcl_get_client_names_and_types_by_node(
+                &self.node,
+                allocator,
+                name.as_ptr(),
+                namespace.as_ptr(),
+                names_and_types,
+            )
+        })
+    }
+
+    /// Count publishers of a topic.
+    /// `topic_name` is expanded as `Node::resolve_topic_name` but remapping rules are not applied.
+    pub fn count_publishers(&self, topic_name: &str) -> Result<usize, DynError> {
+        let topic_name = CString::new(self.resolve_name(topic_name, false, true)?)?;
+        let mut count = 0;
+
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        guard.rcl_count_publishers(&self.node, topic_name.as_ptr(), &mut count)?;
+
+        Ok(size_to_usize(count))
+    }
+
+    /// Count subscribers of a topic.
+    /// `topic_name` is expanded as `Node::resolve_topic_name` but remapping rules are not applied.
+    pub fn count_subscribers(&self, topic_name: &str) -> Result<usize, DynError> {
+        let topic_name = CString::new(self.resolve_name(topic_name, false, true)?)?;
+        let mut count = 0;
+
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        guard.rcl_count_subscribers(&self.node, topic_name.as_ptr(), &mut count)?;
+
+        Ok(size_to_usize(count))
+    }
+
+    /// Get information of publishers of a topic, such as node names, type names, and QoS.
+    /// `topic_name` is expanded as `Node::resolve_topic_name` but remapping rules are not applied.
+    pub fn get_publishers_info_by_topic(
+        &self,
+        topic_name: &str,
+    ) -> Result<Vec<TopicEndpointInfo>, DynError> {
+        let topic_name = CString::new(self.resolve_name(topic_name, false, true)?)?;
+        let result = self.get_endpoint_info(|guard, allocator, info| {
+            guard.rcl_get_publishers_info_by_topic(
+                &self.node,
+                allocator,
+                topic_name.as_ptr(),
+                false,
+                info,
+            )
+        })?;
+        Ok(result)
+    }
+
+    /// Get information of subscribers of a topic, such as node names, type names, and QoS.
+    /// `topic_name` is expanded as `Node::resolve_topic_name` but remapping rules are not applied.
+    pub fn get_subscriptions_info_by_topic(
+        &self,
+        topic_name: &str,
+    ) -> Result<Vec<TopicEndpointInfo>, DynError> {
+        let topic_name = CString::new(self.resolve_name(topic_name, false, true)?)?;
+        let result = self.get_endpoint_info(|guard, allocator, info| {
+            guard.rcl_get_subscriptions_info_by_topic(
+                &self.node,
+                allocator,
+                topic_name.as_ptr(),
+                false,
+                info,
+            )
+        })?;
+        Ok(result)
+    }
+
+    /// Wait until the ROS graph changes asynchronously.
+    /// The ROS graph changes when nodes, publishers, subscribers, servers, or clients
+    /// appear or disappear, or when publishers and subscribers are matched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{error::DynError, node::Node};
+    /// use std::sync::Arc;
+    ///
+    /// async fn watch_nodes(node: Arc<Node>) -> Result<(), DynError> {
+    ///     loop {
+    ///         node.graph_changed().await?;
+    ///         let nodes = node.get_node_names_and_namespaces()?;
+    ///         println!("nodes: {nodes:?}");
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `ContextShutdown` if the context has been shut down, or
+    /// - `Signaled` if a signal has been received.
+    pub async fn graph_changed(self: &Arc<Self>) -> Result<(), DynError> {
+        let cond = GuardCondition::new_graph(self.clone())?;
+        AsyncGraphChange::new(self.context.clone(), cond).await
+    }
+
+    /// Block until `is_ready` returns `true` or `timeout` elapses.
+    /// `is_ready` is evaluated whenever the ROS graph changes.
+    /// This returns `false` if timed out.
+    pub(crate) fn wait_graph<F>(
+        self: &Arc<Self>,
+        timeout: Duration,
+        mut is_ready: F,
+    ) -> Result<bool, DynError>
+    where
+        F: FnMut() -> Result<bool, DynError>,
+    {
+        if is_ready()? {
+            return Ok(true);
+        }
+
+        let cond = GuardCondition::new_graph(self.clone())?;
+        let mut selector = self.context.create_selector()?;
+        selector.add_guard_condition(&cond, None, false);
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(false);
+            }
+
+            selector.wait_timeout(deadline - now)?;
+
+            if is_ready()? {
+                return Ok(true);
+            }
+        }
+    }
+
+    /// Asynchronous version of `Node::wait_graph`, which waits without timeout.
+    pub(crate) async fn wait_graph_async<F>(
+        self: Arc<Self>,
+        mut is_ready: F,
+    ) -> Result<(), DynError>
+    where
+        F: FnMut() -> Result<bool, DynError>,
+    {
+        let cond = GuardCondition::new_graph(self.clone())?;
+        loop {
+            if is_ready()? {
+                return Ok(());
+            }
+
+            AsyncGraphChange::new(self.context.clone(), cond.clone()).await?;
+        }
+    }
+
+    fn get_endpoint_info<F>(&self, f: F) -> RCLResult<Vec<TopicEndpointInfo>>
+    where
+        F: FnOnce(
+            &rcl::MTUnsafeFn,
+            *mut rcl::rcutils_allocator_t,
+            *mut rcl::rcl_topic_endpoint_info_array_t,
+        ) -> RCLResult<()>,
+    {
+        let mut info = rcl::MTSafeFn::rmw_get_zero_initialized_topic_endpoint_info_array();
+        let mut allocator = get_allocator();
+
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        f(&guard, &mut allocator, &mut info)?;
+
+        let result = if info.info_array.is_null() {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(info.info_array, size_to_usize(info.size)) }
+                .iter()
+                .map(|i| i.into())
+                .collect()
+        };
+
+        guard.rmw_topic_endpoint_info_array_fini(&mut info, &mut allocator)?;
+
+        Ok(result)
+    }
+
+    fn get_names_and_types<F>(&self, f: F) -> RCLResult<NamesAndTypes>
+    where
+        F: FnOnce(
+            &rcl::MTUnsafeFn,
+            *mut rcl::rcl_allocator_t,
+            *mut rcl::rcl_names_and_types_t,
+        ) -> RCLResult<()>,
+    {
+        let mut names_and_types = rcl::MTSafeFn::rmw_get_zero_initialized_names_and_types();
+        let mut allocator = get_allocator();
+
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        f(&guard, &mut allocator, &mut names_and_types)?;
+
+        let result = unsafe { names_and_types_to_map(&names_and_types) };
+        guard.rcl_names_and_types_fini(&mut names_and_types)?;
+
+        Ok(result)
+    }
+}
+
+fn node_name_to_cstring(name: &str, namespace: &str) -> RCLResult<(CString, CString)> {
+    let name = CString::new(name).or(Err(RCLError::NodeInvalidName))?;
+    let namespace = CString::new(namespace).or(Err(RCLError::NodeInvalidNamespace))?;
+    Ok((name, namespace))
+}
+
+#[allow(clippy::unnecessary_cast)] // size_t is not usize on galactic
+fn size_to_usize(n: rcl::size_t) -> usize {
+    n as usize
+}
+
+unsafe fn cstr_to_string(s: *const c_char) -> String {
+    CStr::from_ptr(s).to_string_lossy().into_owned()
+}
+
+unsafe fn string_array_to_vec(array: &rcl::rcutils_string_array_t) -> Vec<String> {
+    if array.data.is_null() {
+        return Vec::new();
+    }
+
+    std::slice::from_raw_parts(array.data, size_to_usize(array.size))
+        .iter()
+        .map(|s| cstr_to_string(*s))
+        .collect()
+}
+
+unsafe fn names_and_types_to_map(names_and_types: &rcl::rcl_names_and_types_t) -> NamesAndTypes {
+    let names = string_array_to_vec(&names_and_types.names);
+    if names.is_empty() || names_and_types.types.is_null() {
+        return BTreeMap::new();
+    }
+
+    let types = std::slice::from_raw_parts(names_and_types.types, names.len());
+    names
+        .into_iter()
+        .zip(types.iter().map(|t| string_array_to_vec(t)))
+        .collect()
+}
+
+#[derive(Default)]
+struct GraphChangeState {
+    is_changed: bool,
+    waker: Option<Waker>,
+}
+
+/// Future to wait a change of the ROS graph.
+#[must_use]
+pub(crate) struct AsyncGraphChange {
+    context: Arc<Context>,
+    cond: GuardCondition,
+    state: Option<Arc<Mutex<GraphChangeState>>>,
+}
+
+impl AsyncGraphChange {
+    pub(crate) fn new(context: Arc<Context>, cond: GuardCondition) -> Self {
+        AsyncGraphChange {
+            context,
+            cond,
+            state: None,
+        }
+    }
+}
+
+impl Future for AsyncGraphChange {
+    type Output = Result<(), DynError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        if is_halt() {
+            return Poll::Ready(Err(Signaled.into()));
+        }
+
+        if !self.context.is_valid() {
+            return Poll::Ready(Err(ContextShutdown.into()));
+        }
+
+        let this = self.get_mut();
+
+        if let Some(state) = &this.state {
+            // waiting
+            let mut guard = state.lock();
+            if guard.is_changed {
+                Poll::Ready(Ok(()))
+            } else {
+                guard.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        } else {
+            let state = Arc::new(Mutex::new(GraphChangeState {
+                is_changed: false,
+                waker: Some(cx.waker().clone()),
+            }));
+            let state_cloned = state.clone();
+
+            let mut guard = SELECTOR.lock();
+            if let Err(e) = guard.send_command(
+                &this.context,
+                Command::ConditionVar(
+                    this.cond.clone(),
+                    Box::new(move || {
+                        let mut guard = state_cloned.lock();
+                        guard.is_changed = true;
+                        if let Some(w) = guard.waker.take() {
+                            w.wake();
+                        }
+                        CallbackResult::Remove
+                    }),
+                ),
+            ) {
+                return Poll::Ready(Err(e));
+            }
+
+            this.state = Some(state);
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for AsyncGraphChange {
+    fn drop(&mut self) {
+        if let Some(state) = &self.state {
+            if !state.lock().is_changed {
+                let mut guard = SELECTOR.lock();
+                let _ = guard.send_command(
+                    &self.context,
+                    Command::RemoveConditionVar(self.cond.clone()),
+                );
+            }
+        }
+    }
+}
@@ -125,6 +125,7 @@
 //! ```
 
 use crate::{
+    context::ContextShutdown,
     error::{DynError, RCLResult},
     helper::Contains,
     is_halt,
@@ -146,7 +147,7 @@ use crate::{
     },
     node::Node,
     qos::Profile,
-    rcl::rcl_variant_t,
+    rcl::{self, rcl_variant_t},
     selector::{
         async_selector::{Command, SELECTOR},
         guard_condition::GuardCondition,
@@ -829,6 +830,59 @@ impl Drop for ParameterServer {
     }
 }
 
+/// Collect parameter overrides of the node whose fully qualified name is `node_fqn`.
+/// Overrides collected later take precedence.
+pub(crate) fn collect_overrides(
+    params: &rcl::rcl_params_t,
+    node_fqn: &str,
+    overrides: &mut BTreeMap<String, Value>,
+) {
+    #[allow(clippy::unnecessary_cast)] // size_t is not usize on galactic
+    let num_nodes = params.num_nodes as usize;
+
+    let node_names = unsafe { from_raw_parts(params.node_names, num_nodes) };
+    let node_params = unsafe { from_raw_parts(params.params, num_nodes) };
+
+    for (node_name, node_params) in node_names.iter().zip(node_params.iter()) {
+        let node_name = unsafe { CStr::from_ptr(*node_name) }.to_string_lossy();
+        if !match_node_name(&node_name, node_fqn) {
+            continue;
+        }
+
+        #[allow(clippy::unnecessary_cast)] // size_t is not usize on galactic
+        let num_params = node_params.num_params as usize;
+
+        let names = unsafe { from_raw_parts(node_params.parameter_names, num_params) };
+        let values = unsafe { from_raw_parts(node_params.parameter_values, num_params) };
+
+        for (name, value) in names.iter().zip(values.iter()) {
+            let name = unsafe { CStr::from_ptr(*name) }.to_string_lossy();
+            overrides.insert(name.into_owned(), value.into());
+        }
+    }
+}
+
+/// Check whether a node name of parameter overrides matches `node_fqn`.
+/// `*` matches a token of a name, and `**` matches zero or more tokens.
+fn match_node_name(pattern: &str, node_fqn: &str) -> bool {
+    fn match_tokens(pattern: &[&str], name: &[&str]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some((&"**", rest)) => (0..=name.len()).any(|i| match_tokens(rest, &name[i..])),
+            Some((token, rest)) => match name.split_first() {
+                Some((head, name_rest)) => {
+                    (*token == "*" || token == head) && match_tokens(rest, name_rest)
+                }
+                None => false,
+            },
+        }
+    }
+
+    let pattern: Vec<&str> = pattern.trim_start_matches('/').split('/').collect();
+    let name: Vec<&str> = node_fqn.trim_start_matches('/').split('/').collect();
+    match_tokens(&pattern, &name)
+}
+
 fn param_server(
     node: Arc<Node>,
     params: Arc<RwLock<Parameters>>,
@@ -1249,6 +1303,10 @@ impl<'a> Future for AsyncWait<'a> {
             return Poll::Ready(Err(Signaled.into()));
         }
 
+        if !self.param_server.node.context.is_valid() {
+            return Poll::Ready(Err(ContextShutdown.into()));
+        }
+
         match self.state {
             WaitState::Init => {
                 let mut waker = Some(cx.waker().clone());
@@ -4,6 +4,7 @@ use crate::{
     error::{DynError, RCLResult},
     msg::TypeSupport,
     rcl,
+    topic::publisher::RCLPublisher,
 };
 
 /// A message loaned by a publisher.
@@ -17,8 +18,8 @@ pub enum PublisherLoanedMessage<T: TypeSupport> {
 unsafe impl<T: Send + TypeSupport> Send for PublisherLoanedMessage<T> {}
 
 impl<T: TypeSupport> PublisherLoanedMessage<T> {
-    pub(crate) fn new(publisher: Arc<rcl::rcl_publisher_t>) -> RCLResult<Self> {
-        if rcl::MTSafeFn::rcl_publisher_can_loan_messages(publisher.as_ref() as *const _) {
+    pub(crate) fn new(publisher: Arc<RCLPublisher>) -> RCLResult<Self> {
+        if rcl::MTSafeFn::rcl_publisher_can_loan_messages(&publisher.publisher) {
             Ok(Self::Loaned(Loaned::new(publisher)?))
         } else {
             // Allocate if loaning is not available
@@ -30,7 +31,7 @@ impl<T: TypeSupport> PublisherLoanedMessage<T> {
         match self {
             PublisherLoanedMessage::Copied(msg) => {
                 if let Err(e) = rcl::MTSafeFn::rcl_publish(
-                    msg.publisher.as_ref(),
+                    &msg.publisher.publisher,
                     &msg.value as *const T as _,
                     null_mut(),
                 ) {
@@ -39,7 +40,7 @@ impl<T: TypeSupport> PublisherLoanedMessage<T> {
             }
             PublisherLoanedMessage::Loaned(mut msg) => {
                 if let Err(e) = rcl::MTSafeFn::rcl_publish_loaned_message(
-                    msg.publisher.as_ref(),
+                    &msg.publisher.publisher,
                     msg.as_mut_ptr() as *const _ as *mut _,
                     null_mut(),
                 ) {
@@ -76,28 +77,28 @@ impl<T: TypeSupport> std::ops::DerefMut for PublisherLoanedMessage<T> {
 }
 
 pub struct Copied<T: TypeSupport> {
-    publisher: Arc<rcl::rcl_publisher_t>,
+    publisher: Arc<RCLPublisher>,
     value: T,
 }
 
 impl<T: TypeSupport> Copied<T> {
-    pub fn new(publisher: Arc<rcl::rcl_publisher_t>) -> Self {
+    pub(crate) fn new(publisher: Arc<RCLPublisher>) -> Self {
         let value: T = unsafe { MaybeUninit::zeroed().assume_init() };
         Self { publisher, value }
     }
 }
 
 pub struct Loaned<T: TypeSupport> {
-    publisher: Arc<rcl::rcl_publisher_t>,
+    publisher: Arc<RCLPublisher>,
     chunk: *mut T,
     returned: bool,
 }
 
 impl<T: TypeSupport> Loaned<T> {
-    pub(crate) fn new(publisher: Arc<rcl::rcl_publisher_t>) -> RCLResult<Self> {
+    pub(crate) fn new(publisher: Arc<RCLPublisher>) -> RCLResult<Self> {
         let mut chunk = null_mut();
         let guard = rcl::MT_UNSAFE_FN.lock();
-        guard.rcl_borrow_loaned_message(publisher.as_ref(), T::type_support(), &mut chunk)?;
+        guard.rcl_borrow_loaned_message(&publisher.publisher, T::type_support(), &mut chunk)?;
         Ok(Self {
             publisher,
             chunk: chunk as *mut T,
@@ -122,7 +123,7 @@ impl<T: TypeSupport> Drop for Loaned<T> {
 
         let guard = rcl::MT_UNSAFE_FN.lock();
         let _ = guard.rcl_return_loaned_message_from_publisher(
-            self.publisher.as_ref(),
+            &self.publisher.publisher,
             self.chunk as *const _ as *mut _,
         );
     }
@@ -9,14 +9,49 @@ pub mod humble;
 #[cfg(feature = "iron")]
 pub mod iron;
 
+pub mod overriding;
 pub mod policy;
 
 // pub mod policy;
 
-use crate::rcl;
+use crate::{
+    error::{RCLError, RCLResult},
+    rcl,
+};
 use num_traits::{FromPrimitive, ToPrimitive};
 use policy::*;
-use std::time::Duration;
+use std::{ffi::CStr, os::raw::c_char, time::Duration};
+
+#[cfg(feature = "galactic")]
+use rcl::{
+    rmw_qos_compatibility_type_t_RMW_QOS_COMPATIBILITY_OK as RMW_QOS_COMPATIBILITY_OK,
+    rmw_qos_compatibility_type_t_RMW_QOS_COMPATIBILITY_WARNING as RMW_QOS_COMPATIBILITY_WARNING,
+};
+
+#[cfg(any(feature = "humble", feature = "iron"))]
+use rcl::{
+    rmw_qos_compatibility_type_e_RMW_QOS_COMPATIBILITY_OK as RMW_QOS_COMPATIBILITY_OK,
+    rmw_qos_compatibility_type_e_RMW_QOS_COMPATIBILITY_WARNING as RMW_QOS_COMPATIBILITY_WARNING,
+};
+
+/// `RMW_QOS_DEADLINE_BEST_AVAILABLE` and `RMW_QOS_LIVELINESS_LEASE_DURATION_BEST_AVAILABLE`.
+#[cfg(not(any(feature = "humble", feature = "galactic")))]
+const DURATION_BEST_AVAILABLE: Duration = Duration::new(9223372036, 854775806);
+
+/// Result of `Profile::check_compatible`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Compatibility {
+    /// The profiles are compatible.
+    Ok,
+
+    /// The profiles may not be compatible, for example, because of `SystemDefault` policies.
+    /// This holds the reason.
+    Warning(String),
+
+    /// The profiles are not compatible.
+    /// This holds the reason.
+    Error(String),
+}
 
 /// Represent QoS profile.
 #[derive(Debug, Clone)]
@@ -160,6 +195,165 @@ impl Profile {
             ..Self::common()
         }
     }
+
+    /// Parameter events QoS class
+    /// - History: Keep last,
+    /// - Depth: 1000,
+    /// - Reliability: Reliable,
+    /// - Durability: Volatile,
+    /// - Deadline: Default,
+    /// - Lifespan: Default,
+    /// - Liveliness: System default,
+    /// - Liveliness lease duration: Default,
+    /// - Avoid ros namespace conventions: false
+    pub const fn parameter_events() -> Self {
+        Self {
+            history: HistoryPolicy::KeepLast,
+            depth: 1000,
+            reliability: ReliabilityPolicy::Reliable,
+            durability: DurabilityPolicy::Volatile,
+            ..Self::common()
+        }
+    }
+
+    /// System default QoS class
+    /// - History: System default,
+    /// - Depth: System default,
+    /// - Reliability: System default,
+    /// - Durability: System default,
+    /// - Deadline: Default,
+    /// - Lifespan: Default,
+    /// - Liveliness: System default,
+    /// - Liveliness lease duration: Default,
+    /// - Avoid ros namespace conventions: false
+    pub const fn system_default() -> Self {
+        Self::common()
+    }
+
+    /// Rosout QoS class
+    /// - History: Keep last,
+    /// - Depth: 1000,
+    /// - Reliability: Reliable,
+    /// - Durability: Transient local,
+    /// - Deadline: Default,
+    /// - Lifespan: 10s,
+    /// - Liveliness: System default,
+    /// - Liveliness lease duration: Default,
+    /// - Avoid ros namespace conventions: false
+    pub const fn rosout() -> Self {
+        Self {
+            history: HistoryPolicy::KeepLast,
+            depth: 1000,
+            reliability: ReliabilityPolicy::Reliable,
+            durability: DurabilityPolicy::TransientLocal,
+            lifespan: Duration::from_secs(10),
+            ..Self::common()
+        }
+    }
+
+    /// Clock QoS class
+    /// - History: Keep last,
+    /// - Depth: 1,
+    /// - Reliability: Best effort,
+    /// - Durability: Volatile,
+    /// - Deadline: Default,
+    /// - Lifespan: Default,
+    /// - Liveliness: System default,
+    /// - Liveliness lease duration: Default,
+    /// - Avoid ros namespace conventions: false
+    pub const fn clock() -> Self {
+        Self {
+            history: HistoryPolicy::KeepLast,
+            depth: 1,
+            reliability: ReliabilityPolicy::BestEffort,
+            durability: DurabilityPolicy::Volatile,
+            ..Self::common()
+        }
+    }
+
+    /// Best available QoS class, which matches the majority of endpoints
+    /// while keeping the highest level of service possible
+    /// - History: Keep last,
+    /// - Depth: 10,
+    /// - Reliability: Best available,
+    /// - Durability: Best available,
+    /// - Deadline: Best available,
+    /// - Lifespan: Default,
+    /// - Liveliness: Best available,
+    /// - Liveliness lease duration: Best available,
+    /// - Avoid ros namespace conventions: false
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub const fn best_available() -> Self {
+        Self {
+            history: HistoryPolicy::KeepLast,
+            depth: 10,
+            reliability: ReliabilityPolicy::BestAvailable,
+            durability: DurabilityPolicy::BestAvailable,
+            deadline: DURATION_BEST_AVAILABLE,
+            liveliness: LivelinessPolicy::BestAvailable,
+            liveliness_lease_duration: DURATION_BEST_AVAILABLE,
+            ..Self::common()
+        }
+    }
+
+    /// Check whether a publisher of `publisher` and a subscriber of `subscription`
+    /// can communicate with each other.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::qos::{Compatibility, Profile};
+    ///
+    /// // A reliable subscriber cannot receive messages from a best effort publisher.
+    /// let result = Profile::check_compatible(&Profile::sensor_data(), &Profile::default()).unwrap();
+    /// assert!(matches!(result, Compatibility::Error(_)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn check_compatible(
+        publisher: &Profile,
+        subscription: &Profile,
+    ) -> RCLResult<Compatibility> {
+        let mut compatibility = RMW_QOS_COMPATIBILITY_OK;
+        let mut reason: [c_char; 2048] = [0; 2048];
+
+        #[allow(clippy::unnecessary_cast)] // size_t is not usize on galactic
+        rcl::MTSafeFn::rmw_qos_profile_check_compatible(
+            publisher.into(),
+            subscription.into(),
+            &mut compatibility,
+            reason.as_mut_ptr(),
+            (reason.len() - 1) as rcl::size_t, // keep the null terminator
+        )?;
+
+        let reason = unsafe { CStr::from_ptr(reason.as_ptr()) }
+            .to_string_lossy()
+            .into_owned();
+
+        let result = match compatibility {
+            RMW_QOS_COMPATIBILITY_OK => Compatibility::Ok,
+            RMW_QOS_COMPATIBILITY_WARNING => Compatibility::Warning(reason),
+            _ => Compatibility::Error(reason),
+        };
+
+        Ok(result)
+    }
+
+    /// Convert a profile returned by `rcl_*_get_actual_qos`,
+    /// which returns null if the entity is invalid.
+    pub(crate) fn from_actual_qos(
+        qos: *const rcl::rmw_qos_profile_t,
+        err_invalid: RCLError,
+    ) -> RCLResult<Self> {
+        if qos.is_null() {
+            Err(err_invalid)
+        } else {
+            Ok(unsafe { &*qos }.into())
+        }
+    }
 }
 
 impl From<&rcl::rmw_qos_profile_t> for Profile {
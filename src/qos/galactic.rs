@@ -77,3 +77,20 @@ pub enum LivelinessPolicy {
     /// Liveliness policy has not yet been set
     Unknown = rcl::rmw_qos_liveliness_policy_t_RMW_QOS_POLICY_LIVELINESS_UNKNOWN,
 }
+
+/// Kinds of QoS policies, which are reported by incompatible QoS events.
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum PolicyKind {
+    Invalid = rcl::rmw_qos_policy_kind_t_RMW_QOS_POLICY_INVALID,
+    Durability = rcl::rmw_qos_policy_kind_t_RMW_QOS_POLICY_DURABILITY,
+    Deadline = rcl::rmw_qos_policy_kind_t_RMW_QOS_POLICY_DEADLINE,
+    Liveliness = rcl::rmw_qos_policy_kind_t_RMW_QOS_POLICY_LIVELINESS,
+    Reliability = rcl::rmw_qos_policy_kind_t_RMW_QOS_POLICY_RELIABILITY,
+    History = rcl::rmw_qos_policy_kind_t_RMW_QOS_POLICY_HISTORY,
+    Lifespan = rcl::rmw_qos_policy_kind_t_RMW_QOS_POLICY_LIFESPAN,
+    Depth = rcl::rmw_qos_policy_kind_t_RMW_QOS_POLICY_DEPTH,
+    LivelinessLeaseDuration = rcl::rmw_qos_policy_kind_t_RMW_QOS_POLICY_LIVELINESS_LEASE_DURATION,
+    AvoidRosNamespaceConventions =
+        rcl::rmw_qos_policy_kind_t_RMW_QOS_POLICY_AVOID_ROS_NAMESPACE_CONVENTIONS,
+}
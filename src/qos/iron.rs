@@ -33,6 +33,9 @@ pub enum ReliabilityPolicy {
 
     /// Reliability policy has not yet been set
     Unknown = rcl::rmw_qos_reliability_policy_e_RMW_QOS_POLICY_RELIABILITY_UNKNOWN,
+
+    /// Will match the majority of endpoints and use a reliable policy if possible
+    BestAvailable = rcl::rmw_qos_reliability_policy_e_RMW_QOS_POLICY_RELIABILITY_BEST_AVAILABLE,
 }
 
 /// QoS durability enumerations describing how samples persist
@@ -50,6 +53,9 @@ pub enum DurabilityPolicy {
 
     /// Durability policy has not yet been set
     Unknown = rcl::rmw_qos_durability_policy_e_RMW_QOS_POLICY_DURABILITY_UNKNOWN,
+
+    /// Will match the majority of endpoints and use a transient local policy if possible
+    BestAvailable = rcl::rmw_qos_durability_policy_e_RMW_QOS_POLICY_DURABILITY_BEST_AVAILABLE,
 }
 
 /// QoS liveliness enumerations that describe a publisher's reporting policy for its alive status.
@@ -76,4 +82,24 @@ pub enum LivelinessPolicy {
 
     /// Liveliness policy has not yet been set
     Unknown = rcl::rmw_qos_liveliness_policy_e_RMW_QOS_POLICY_LIVELINESS_UNKNOWN,
+
+    /// Will match the majority of endpoints and use a manual by topic policy if possible
+    BestAvailable = rcl::rmw_qos_liveliness_policy_e_RMW_QOS_POLICY_LIVELINESS_BEST_AVAILABLE,
+}
+
+/// Kinds of QoS policies, which are reported by incompatible QoS events.
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum PolicyKind {
+    Invalid = rcl::rmw_qos_policy_kind_e_RMW_QOS_POLICY_INVALID,
+    Durability = rcl::rmw_qos_policy_kind_e_RMW_QOS_POLICY_DURABILITY,
+    Deadline = rcl::rmw_qos_policy_kind_e_RMW_QOS_POLICY_DEADLINE,
+    Liveliness = rcl::rmw_qos_policy_kind_e_RMW_QOS_POLICY_LIVELINESS,
+    Reliability = rcl::rmw_qos_policy_kind_e_RMW_QOS_POLICY_RELIABILITY,
+    History = rcl::rmw_qos_policy_kind_e_RMW_QOS_POLICY_HISTORY,
+    Lifespan = rcl::rmw_qos_policy_kind_e_RMW_QOS_POLICY_LIFESPAN,
+    Depth = rcl::rmw_qos_policy_kind_e_RMW_QOS_POLICY_DEPTH,
+    LivelinessLeaseDuration = rcl::rmw_qos_policy_kind_e_RMW_QOS_POLICY_LIVELINESS_LEASE_DURATION,
+    AvoidRosNamespaceConventions =
+        rcl::rmw_qos_policy_kind_e_RMW_QOS_POLICY_AVOID_ROS_NAMESPACE_CONVENTIONS,
 }
@@ -0,0 +1,290 @@
+//! QoS overrides through parameters.
+//!
+//! Publishers and subscribers created by `Node::create_publisher_with_qos_overrides` and
+//! `Node::create_subscriber_with_qos_overrides` read parameters compatible with rclcpp,
+//! such as `qos_overrides./chatter.publisher.reliability`, when they are created.
+//! The parameters are given by command line arguments, parameter files,
+//! or `NodeOptionsBuilder::parameter`, and only policies specified by
+//! `QoSOverridingOptions` can be overridden.
+//!
+//! If the node has a `ParameterServer`, parameters of the server take precedence,
+//! and the overridable policies are declared in the server as read-only parameters
+//! holding the resulting QoS, as rclcpp does.
+//! Because QoS cannot be changed after creation,
+//! only a `ParameterServer` created before the publisher or subscriber is consulted.
+//!
+//! | Parameter                         | Value                                                  |
+//! |-----------------------------------|--------------------------------------------------------|
+//! | `history`                         | `keep_last`, `keep_all`, or `system_default`           |
+//! | `depth`                           | integer                                                |
+//! | `reliability`                     | `reliable`, `best_effort`, or `system_default`         |
+//! | `durability`                      | `volatile`, `transient_local`, or `system_default`     |
+//! | `liveliness`                      | `automatic`, `manual_by_topic`, or `system_default`    |
+//! | `deadline`                        | integer in nanoseconds                                 |
+//! | `lifespan`                        | integer in nanoseconds                                 |
+//! | `liveliness_lease_duration`       | integer in nanoseconds                                 |
+//! | `avoid_ros_namespace_conventions` | bool                                                   |
+//!
+//! `best_available` is also accepted as reliability, durability, and liveliness on iron.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::{
+//!     context::Context,
+//!     msg::common_interfaces::std_msgs,
+//!     node::NodeOptions,
+//!     qos::{overriding::QoSOverridingOptions, policy::ReliabilityPolicy},
+//! };
+//!
+//! let ctx = Context::new().unwrap();
+//!
+//! // Equivalent to `--ros-args -p qos_overrides./overriding_rs_topic.publisher.reliability:=best_effort`.
+//! let options = NodeOptions::builder()
+//!     .parameter("qos_overrides./overriding_rs_topic.publisher.reliability", "best_effort")
+//!     .build()
+//!     .unwrap();
+//!
+//! let node = ctx
+//!     .create_node("overriding_rs", None, options)
+//!     .unwrap();
+//!
+//! // Create a publisher whose history, depth, and reliability can be overridden.
+//! let publisher = node
+//!     .create_publisher_with_qos_overrides::<std_msgs::msg::UInt32>(
+//!         "overriding_rs_topic",
+//!         None,
+//!         &QoSOverridingOptions::with_default_policies(),
+//!         #[cfg(not(any(feature = "humble", feature = "galactic")))]
+//!         true,
+//!     )
+//!     .unwrap();
+//!
+//! let qos = publisher.get_actual_qos().unwrap();
+//! assert_eq!(qos.reliability, ReliabilityPolicy::BestEffort);
+//! ```
+
+use super::{policy::*, Profile};
+use crate::{
+    error::DynError,
+    parameter::{Parameters, Value},
+};
+use std::{collections::BTreeMap, time::Duration};
+
+/// Policies which can be overridden by parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QoSOverridingOptions {
+    policies: Vec<PolicyKind>,
+    id: Option<String>,
+}
+
+impl QoSOverridingOptions {
+    /// Allow `policies` to be overridden.
+    pub fn new(policies: &[PolicyKind]) -> Self {
+        QoSOverridingOptions {
+            policies: policies.to_vec(),
+            id: None,
+        }
+    }
+
+    /// Allow history, depth, and reliability to be overridden,
+    /// which are the default policies of rclcpp.
+    pub fn with_default_policies() -> Self {
+        Self::new(&[
+            PolicyKind::History,
+            PolicyKind::Depth,
+            PolicyKind::Reliability,
+        ])
+    }
+
+    /// Set the ID to distinguish publishers or subscribers of the same topic in a node.
+    /// If `id` is specified, the parameters are `qos_overrides./topic.publisher_<id>.<policy>`.
+    pub fn id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    /// Override `profile` by `params` or `overrides`, and declare the result in `params`.
+    /// `topic_name` must be the resolved name,
+    /// and `entity` is `"publisher"` or `"subscription"`.
+    pub(crate) fn apply(
+        &self,
+        mut profile: Profile,
+        topic_name: &str,
+        entity: &str,
+        overrides: &BTreeMap<String, Value>,
+        params: Option<&mut Parameters>,
+    ) -> Result<Profile, DynError> {
+        let prefix = match &self.id {
+            Some(id) => format!("qos_overrides.{topic_name}.{entity}_{id}"),
+            None => format!("qos_overrides.{topic_name}.{entity}"),
+        };
+
+        for kind in self.policies.iter() {
+            let Some(policy_name) = policy_name(*kind) else {
+                continue;
+            };
+
+            let name = format!("{prefix}.{policy_name}");
+            let param = params
+                .as_deref()
+                .and_then(|params| params.get_parameter(&name))
+                .map(|param| &param.value);
+            let Some(value) = param.or_else(|| overrides.get(&name)) else {
+                continue;
+            };
+
+            if !override_policy(&mut profile, *kind, value) {
+                let msg = format!("invalid QoS override: {name} = {value}");
+                return Err(msg.into());
+            }
+        }
+
+        if let Some(params) = params {
+            for kind in self.policies.iter() {
+                let Some(policy_name) = policy_name(*kind) else {
+                    continue;
+                };
+
+                let name = format!("{prefix}.{policy_name}");
+                if params.get_parameter(&name).is_none() {
+                    let value = policy_value(&profile, *kind);
+                    params.set_parameter(name, value, true, None)?;
+                }
+            }
+        }
+
+        Ok(profile)
+    }
+}
+
+fn policy_name(kind: PolicyKind) -> Option<&'static str> {
+    let name = match kind {
+        PolicyKind::History => "history",
+        PolicyKind::Depth => "depth",
+        PolicyKind::Reliability => "reliability",
+        PolicyKind::Durability => "durability",
+        PolicyKind::Liveliness => "liveliness",
+        PolicyKind::Deadline => "deadline",
+        PolicyKind::Lifespan => "lifespan",
+        PolicyKind::LivelinessLeaseDuration => "liveliness_lease_duration",
+        PolicyKind::AvoidRosNamespaceConventions => "avoid_ros_namespace_conventions",
+        PolicyKind::Invalid => return None,
+    };
+    Some(name)
+}
+
+/// Value of the parameter of `kind` representing `profile`.
+fn policy_value(profile: &Profile, kind: PolicyKind) -> Value {
+    let name = match kind {
+        PolicyKind::History => match profile.history {
+            HistoryPolicy::KeepLast => "keep_last",
+            HistoryPolicy::KeepAll => "keep_all",
+            HistoryPolicy::SystemDefault => "system_default",
+            _ => "unknown",
+        },
+        PolicyKind::Reliability => match profile.reliability {
+            ReliabilityPolicy::Reliable => "reliable",
+            ReliabilityPolicy::BestEffort => "best_effort",
+            ReliabilityPolicy::SystemDefault => "system_default",
+
+            #[cfg(not(any(feature = "humble", feature = "galactic")))]
+            ReliabilityPolicy::BestAvailable => "best_available",
+
+            _ => "unknown",
+        },
+        PolicyKind::Durability => match profile.durability {
+            DurabilityPolicy::Volatile => "volatile",
+            DurabilityPolicy::TransientLocal => "transient_local",
+            DurabilityPolicy::SystemDefault => "system_default",
+
+            #[cfg(not(any(feature = "humble", feature = "galactic")))]
+            DurabilityPolicy::BestAvailable => "best_available",
+
+            _ => "unknown",
+        },
+        PolicyKind::Liveliness => match profile.liveliness {
+            LivelinessPolicy::Automatic => "automatic",
+            LivelinessPolicy::ManualByTopic => "manual_by_topic",
+            LivelinessPolicy::SystemDefault => "system_default",
+
+            #[cfg(not(any(feature = "humble", feature = "galactic")))]
+            LivelinessPolicy::BestAvailable => "best_available",
+
+            _ => "unknown",
+        },
+        PolicyKind::Depth => return Value::I64(profile.depth as i64),
+        PolicyKind::Deadline => return duration_value(profile.deadline),
+        PolicyKind::Lifespan => return duration_value(profile.lifespan),
+        PolicyKind::LivelinessLeaseDuration => {
+            return duration_value(profile.liveliness_lease_duration)
+        }
+        PolicyKind::AvoidRosNamespaceConventions => {
+            return Value::Bool(profile.avoid_ros_namespace_conventions)
+        }
+        PolicyKind::Invalid => return Value::NotSet,
+    };
+    Value::String(name.to_string())
+}
+
+fn duration_value(duration: Duration) -> Value {
+    Value::I64(duration.as_nanos().try_into().unwrap_or(i64::MAX))
+}
+
+/// Return `false` if `value` is invalid.
+fn override_policy(profile: &mut Profile, kind: PolicyKind, value: &Value) -> bool {
+    match (kind, value) {
+        (PolicyKind::History, Value::String(s)) => match s.as_str() {
+            "keep_last" => profile.history = HistoryPolicy::KeepLast,
+            "keep_all" => profile.history = HistoryPolicy::KeepAll,
+            "system_default" => profile.history = HistoryPolicy::SystemDefault,
+            _ => return false,
+        },
+        (PolicyKind::Depth, Value::I64(n)) if *n >= 0 => profile.depth = *n as usize,
+        (PolicyKind::Reliability, Value::String(s)) => match s.as_str() {
+            "reliable" => profile.reliability = ReliabilityPolicy::Reliable,
+            "best_effort" => profile.reliability = ReliabilityPolicy::BestEffort,
+            "system_default" => profile.reliability = ReliabilityPolicy::SystemDefault,
+
+            #[cfg(not(any(feature = "humble", feature = "galactic")))]
+            "best_available" => profile.reliability = ReliabilityPolicy::BestAvailable,
+
+            _ => return false,
+        },
+        (PolicyKind::Durability, Value::String(s)) => match s.as_str() {
+            "volatile" => profile.durability = DurabilityPolicy::Volatile,
+            "transient_local" => profile.durability = DurabilityPolicy::TransientLocal,
+            "system_default" => profile.durability = DurabilityPolicy::SystemDefault,
+
+            #[cfg(not(any(feature = "humble", feature = "galactic")))]
+            "best_available" => profile.durability = DurabilityPolicy::BestAvailable,
+
+            _ => return false,
+        },
+        (PolicyKind::Liveliness, Value::String(s)) => match s.as_str() {
+            "automatic" => profile.liveliness = LivelinessPolicy::Automatic,
+            "manual_by_topic" => profile.liveliness = LivelinessPolicy::ManualByTopic,
+            "system_default" => profile.liveliness = LivelinessPolicy::SystemDefault,
+
+            #[cfg(not(any(feature = "humble", feature = "galactic")))]
+            "best_available" => profile.liveliness = LivelinessPolicy::BestAvailable,
+
+            _ => return false,
+        },
+        (PolicyKind::Deadline, Value::I64(n)) if *n >= 0 => {
+            profile.deadline = Duration::from_nanos(*n as u64)
+        }
+        (PolicyKind::Lifespan, Value::I64(n)) if *n >= 0 => {
+            profile.lifespan = Duration::from_nanos(*n as u64)
+        }
+        (PolicyKind::LivelinessLeaseDuration, Value::I64(n)) if *n >= 0 => {
+            profile.liveliness_lease_duration = Duration::from_nanos(*n as u64)
+        }
+        (PolicyKind::AvoidRosNamespaceConventions, Value::Bool(b)) => {
+            profile.avoid_ros_namespace_conventions = *b
+        }
+        _ => return false,
+    }
+
+    true
+}
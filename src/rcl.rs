@@ -52,6 +52,13 @@ pub use iron::{
 #[cfg(feature = "iron")]
 pub type size_t = usize;
 
+// `rcl_yaml_param_parser/parser.h` is not an input of bindgen,
+// so functions of it are declared here.
+extern "C" {
+    /// Free parameter structure.
+    fn rcl_yaml_node_struct_fini(params_st: *mut rcl_params_t);
+}
+
 use crate::error::{action_ret_val_to_err, ret_val_to_err, RCLActionResult, RCLResult};
 use once_cell::sync::Lazy;
 use parking_lot::Mutex;
@@ -96,6 +103,33 @@ impl MTUnsafeFn {
         ret_val_to_err(unsafe { self::rcl_init_options_fini(init_options) })
     }
 
+    pub fn rcl_init_options_set_domain_id(
+        &self,
+        init_options: *mut rcl_init_options_t,
+        domain_id: usize,
+    ) -> RCLResult<()> {
+        // size_t is not usize on galactic
+        ret_val_to_err(unsafe {
+            self::rcl_init_options_set_domain_id(init_options, domain_id as _)
+        })
+    }
+
+    pub fn rcl_init_options_get_rmw_init_options(
+        &self,
+        init_options: *mut rcl_init_options_t,
+    ) -> *mut rmw_init_options_t {
+        unsafe { self::rcl_init_options_get_rmw_init_options(init_options) }
+    }
+
+    pub fn rcl_context_get_domain_id(
+        &self,
+        context: *mut rcl_context_t,
+        domain_id: *mut usize,
+    ) -> RCLResult<()> {
+        // size_t is not usize on galactic
+        ret_val_to_err(unsafe { self::rcl_context_get_domain_id(context, domain_id as _) })
+    }
+
     pub fn rcl_node_init(
         &self,
         node: *mut rcl_node_t,
@@ -115,6 +149,52 @@ impl MTUnsafeFn {
         ret_val_to_err(unsafe { self::rcl_node_options_fini(options) })
     }
 
+    pub fn rcl_node_get_fully_qualified_name(
+        &self,
+        node: *const rcl_node_t,
+    ) -> *const ::std::os::raw::c_char {
+        unsafe { self::rcl_node_get_fully_qualified_name(node) }
+    }
+
+    pub fn rcl_node_get_options(&self, node: *const rcl_node_t) -> *const rcl_node_options_t {
+        unsafe { self::rcl_node_get_options(node) }
+    }
+
+    pub fn rcl_arguments_get_param_overrides(
+        &self,
+        arguments: *const rcl_arguments_t,
+        parameter_overrides: *mut *mut rcl_params_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_arguments_get_param_overrides(arguments, parameter_overrides)
+        })
+    }
+
+    pub fn rcl_yaml_node_struct_fini(&self, params_st: *mut rcl_params_t) {
+        unsafe { self::rcl_yaml_node_struct_fini(params_st) }
+    }
+
+    pub fn rcl_node_resolve_name(
+        &self,
+        node: *const rcl_node_t,
+        input_name: *const ::std::os::raw::c_char,
+        allocator: rcl_allocator_t,
+        is_service: bool,
+        only_expand: bool,
+        output_name: *mut *mut ::std::os::raw::c_char,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_node_resolve_name(
+                node,
+                input_name,
+                allocator,
+                is_service,
+                only_expand,
+                output_name,
+            )
+        })
+    }
+
     pub fn rcl_publisher_init(
         &self,
         publisher: *mut rcl_publisher_t,
@@ -136,6 +216,20 @@ impl MTUnsafeFn {
         ret_val_to_err(unsafe { self::rcl_publisher_fini(publisher, node) })
     }
 
+    pub fn rcl_publisher_get_topic_name(
+        &self,
+        publisher: *const rcl_publisher_t,
+    ) -> *const ::std::os::raw::c_char {
+        unsafe { self::rcl_publisher_get_topic_name(publisher) }
+    }
+
+    pub fn rcl_publisher_get_actual_qos(
+        &self,
+        publisher: *const rcl_publisher_t,
+    ) -> *const rmw_qos_profile_t {
+        unsafe { self::rcl_publisher_get_actual_qos(publisher) }
+    }
+
     pub fn rcl_subscription_fini(
         &self,
         subscription: *mut rcl_subscription_t,
@@ -157,6 +251,95 @@ impl MTUnsafeFn {
         })
     }
 
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub fn rcl_subscription_options_set_content_filter_options(
+        &self,
+        filter_expression: *const ::std::os::raw::c_char,
+        expression_parameters_argc: usize,
+        expression_parameter_argv: *mut *const ::std::os::raw::c_char,
+        options: *mut rcl_subscription_options_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_subscription_options_set_content_filter_options(
+                filter_expression,
+                expression_parameters_argc,
+                expression_parameter_argv,
+                options,
+            )
+        })
+    }
+
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub fn rcl_subscription_options_fini(
+        &self,
+        options: *mut rcl_subscription_options_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_subscription_options_fini(options) })
+    }
+
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub fn rcl_subscription_content_filter_options_init(
+        &self,
+        subscription: *const rcl_subscription_t,
+        filter_expression: *const ::std::os::raw::c_char,
+        expression_parameters_argc: usize,
+        expression_parameter_argv: *mut *const ::std::os::raw::c_char,
+        options: *mut rcl_subscription_content_filter_options_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_subscription_content_filter_options_init(
+                subscription,
+                filter_expression,
+                expression_parameters_argc,
+                expression_parameter_argv,
+                options,
+            )
+        })
+    }
+
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub fn rcl_subscription_content_filter_options_fini(
+        &self,
+        subscription: *const rcl_subscription_t,
+        options: *mut rcl_subscription_content_filter_options_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_subscription_content_filter_options_fini(subscription, options)
+        })
+    }
+
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub fn rcl_subscription_set_content_filter(
+        &self,
+        subscription: *const rcl_subscription_t,
+        options: *const rcl_subscription_content_filter_options_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_subscription_set_content_filter(subscription, options) })
+    }
+
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub fn rcl_subscription_get_content_filter(
+        &self,
+        subscription: *const rcl_subscription_t,
+        options: *mut rcl_subscription_content_filter_options_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_subscription_get_content_filter(subscription, options) })
+    }
+
+    pub fn rcl_subscription_get_topic_name(
+        &self,
+        subscription: *const rcl_subscription_t,
+    ) -> *const ::std::os::raw::c_char {
+        unsafe { self::rcl_subscription_get_topic_name(subscription) }
+    }
+
+    pub fn rcl_subscription_get_actual_qos(
+        &self,
+        subscription: *const rcl_subscription_t,
+    ) -> *const rmw_qos_profile_t {
+        unsafe { self::rcl_subscription_get_actual_qos(subscription) }
+    }
+
     pub fn rcl_take(
         &self,
         subscription: *const rcl_subscription_t,
@@ -169,6 +352,64 @@ impl MTUnsafeFn {
         })
     }
 
+    pub fn rcl_take_sequence(
+        &self,
+        subscription: *const rcl_subscription_t,
+        count: size_t,
+        message_sequence: *mut rmw_message_sequence_t,
+        message_info_sequence: *mut rmw_message_info_sequence_t,
+        allocation: *mut rmw_subscription_allocation_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_take_sequence(
+                subscription,
+                count,
+                message_sequence,
+                message_info_sequence,
+                allocation,
+            )
+        })
+    }
+
+    pub fn rcl_take_serialized_message(
+        &self,
+        subscription: *const rcl_subscription_t,
+        serialized_message: *mut rcl_serialized_message_t,
+        message_info: *mut rmw_message_info_t,
+        allocation: *mut rmw_subscription_allocation_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_take_serialized_message(
+                subscription,
+                serialized_message,
+                message_info,
+                allocation,
+            )
+        })
+    }
+
+    pub fn rmw_serialize(
+        &self,
+        ros_message: *const ::std::os::raw::c_void,
+        type_support: *const rosidl_message_type_support_t,
+        serialized_message: *mut rmw_serialized_message_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rmw_serialize(ros_message, type_support, serialized_message)
+        })
+    }
+
+    pub fn rmw_deserialize(
+        &self,
+        serialized_message: *const rmw_serialized_message_t,
+        type_support: *const rosidl_message_type_support_t,
+        ros_message: *mut ::std::os::raw::c_void,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rmw_deserialize(serialized_message, type_support, ros_message)
+        })
+    }
+
     pub fn rcl_wait_set_init(
         &self,
         wait_set: *mut rcl_wait_set_t,
@@ -223,6 +464,47 @@ impl MTUnsafeFn {
         })
     }
 
+    pub fn rcl_publisher_event_init(
+        &self,
+        event: *mut rcl_event_t,
+        publisher: *const rcl_publisher_t,
+        event_type: rcl_publisher_event_type_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_publisher_event_init(event, publisher, event_type) })
+    }
+
+    pub fn rcl_subscription_event_init(
+        &self,
+        event: *mut rcl_event_t,
+        subscription: *const rcl_subscription_t,
+        event_type: rcl_subscription_event_type_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_subscription_event_init(event, subscription, event_type)
+        })
+    }
+
+    pub fn rcl_take_event(
+        &self,
+        event: *const rcl_event_t,
+        event_info: *mut ::std::os::raw::c_void,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_take_event(event, event_info) })
+    }
+
+    pub fn rcl_event_fini(&self, event: *mut rcl_event_t) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_event_fini(event) })
+    }
+
+    pub fn rcl_wait_set_add_event(
+        &self,
+        wait_set: *mut rcl_wait_set_t,
+        event: *const rcl_event_t,
+        index: *mut size_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_wait_set_add_event(wait_set, event, index) })
+    }
+
     pub fn rcl_wait_set_add_subscription(
         &self,
         wait_set: *mut rcl_wait_set_t,
@@ -293,6 +575,44 @@ impl MTUnsafeFn {
         ret_val_to_err(unsafe { self::rcl_service_fini(service, node) })
     }
 
+    #[cfg(any(feature = "humble", feature = "iron"))]
+    pub fn rcl_service_request_subscription_get_actual_qos(
+        &self,
+        service: *const rcl_service_t,
+    ) -> *const rmw_qos_profile_t {
+        unsafe { self::rcl_service_request_subscription_get_actual_qos(service) }
+    }
+
+    #[cfg(any(feature = "humble", feature = "iron"))]
+    pub fn rcl_service_response_publisher_get_actual_qos(
+        &self,
+        service: *const rcl_service_t,
+    ) -> *const rmw_qos_profile_t {
+        unsafe { self::rcl_service_response_publisher_get_actual_qos(service) }
+    }
+
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub fn rcl_service_configure_service_introspection(
+        &self,
+        service: *mut rcl_service_t,
+        node: *mut rcl_node_t,
+        clock: *mut rcl_clock_t,
+        type_support: *const rosidl_service_type_support_t,
+        publisher_options: rcl_publisher_options_t,
+        introspection_state: rcl_service_introspection_state_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_service_configure_service_introspection(
+                service,
+                node,
+                clock,
+                type_support,
+                publisher_options,
+                introspection_state,
+            )
+        })
+    }
+
     pub fn rcl_take_request_with_info(
         &self,
         service: *const rcl_service_t,
@@ -325,6 +645,44 @@ impl MTUnsafeFn {
         ret_val_to_err(unsafe { self::rcl_client_fini(client, node) })
     }
 
+    #[cfg(any(feature = "humble", feature = "iron"))]
+    pub fn rcl_client_request_publisher_get_actual_qos(
+        &self,
+        client: *const rcl_client_t,
+    ) -> *const rmw_qos_profile_t {
+        unsafe { self::rcl_client_request_publisher_get_actual_qos(client) }
+    }
+
+    #[cfg(any(feature = "humble", feature = "iron"))]
+    pub fn rcl_client_response_subscription_get_actual_qos(
+        &self,
+        client: *const rcl_client_t,
+    ) -> *const rmw_qos_profile_t {
+        unsafe { self::rcl_client_response_subscription_get_actual_qos(client) }
+    }
+
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub fn rcl_client_configure_service_introspection(
+        &self,
+        client: *mut rcl_client_t,
+        node: *mut rcl_node_t,
+        clock: *mut rcl_clock_t,
+        type_support: *const rosidl_service_type_support_t,
+        publisher_options: rcl_publisher_options_t,
+        introspection_state: rcl_service_introspection_state_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_client_configure_service_introspection(
+                client,
+                node,
+                clock,
+                type_support,
+                publisher_options,
+                introspection_state,
+            )
+        })
+    }
+
     pub fn rcl_take_response_with_info(
         &self,
         client: *const rcl_client_t,
@@ -733,6 +1091,217 @@ impl MTUnsafeFn {
         })
     }
 
+    pub fn rcl_get_topic_names_and_types(
+        &self,
+        node: *const rcl_node_t,
+        allocator: *mut rcl_allocator_t,
+        no_demangle: bool,
+        topic_names_and_types: *mut rcl_names_and_types_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_get_topic_names_and_types(node, allocator, no_demangle, topic_names_and_types)
+        })
+    }
+
+    pub fn rcl_get_service_names_and_types(
+        &self,
+        node: *const rcl_node_t,
+        allocator: *mut rcl_allocator_t,
+        service_names_and_types: *mut rcl_names_and_types_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_get_service_names_and_types(node, allocator, service_names_and_types)
+        })
+    }
+
+    pub fn rcl_get_node_names(
+        &self,
+        node: *const rcl_node_t,
+        allocator: rcl_allocator_t,
+        node_names: *mut rcutils_string_array_t,
+        node_namespaces: *mut rcutils_string_array_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_get_node_names(node, allocator, node_names, node_namespaces)
+        })
+    }
+
+    pub fn rcl_get_publisher_names_and_types_by_node(
+        &self,
+        node: *const rcl_node_t,
+        allocator: *mut rcl_allocator_t,
+        no_demangle: bool,
+        node_name: *const ::std::os::raw::c_char,
+        node_namespace: *const ::std::os::raw::c_char,
+        topic_names_and_types: *mut rcl_names_and_types_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_get_publisher_names_and_types_by_node(
+                node,
+                allocator,
+                no_demangle,
+                node_name,
+                node_namespace,
+                topic_names_and_types,
+            )
+        })
+    }
+
+    pub fn rcl_get_subscriber_names_and_types_by_node(
+        &self,
+        node: *const rcl_node_t,
+        allocator: *mut rcl_allocator_t,
+        no_demangle: bool,
+        node_name: *const ::std::os::raw::c_char,
+        node_namespace: *const ::std::os::raw::c_char,
+        topic_names_and_types: *mut rcl_names_and_types_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_get_subscriber_names_and_types_by_node(
+                node,
+                allocator,
+                no_demangle,
+                node_name,
+                node_namespace,
+                topic_names_and_types,
+            )
+        })
+    }
+
+    pub fn rcl_get_service_names_and_types_by_node(
+        &self,
+        node: *const rcl_node_t,
+        allocator: *mut rcl_allocator_t,
+        node_name: *const ::std::os::raw::c_char,
+        node_namespace: *const ::std::os::raw::c_char,
+        service_names_and_types: *mut rcl_names_and_types_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_get_service_names_and_types_by_node(
+                node,
+                allocator,
+                node_name,
+                node_namespace,
+                service_names_and_types,
+            )
+        })
+    }
+
+    pub fn rcl_get_client_names_and_types_by_node(
+        &self,
+        node: *const rcl_node_t,
+        allocator: *mut rcl_allocator_t,
+        node_name: *const ::std::os::raw::c_char,
+        node_namespace: *const ::std::os::raw::c_char,
+        service_names_and_types: *mut rcl_names_and_types_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_get_client_names_and_types_by_node(
+                node,
+                allocator,
+                node_name,
+                node_namespace,
+                service_names_and_types,
+            )
+        })
+    }
+
+    pub fn rcl_names_and_types_fini(
+        &self,
+        names_and_types: *mut rcl_names_and_types_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_names_and_types_fini(names_and_types) })
+    }
+
+    pub fn rcutils_string_array_fini(
+        &self,
+        string_array: *mut rcutils_string_array_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcutils_string_array_fini(string_array) })
+    }
+
+    pub fn rcl_node_get_graph_guard_condition(
+        &self,
+        node: *const rcl_node_t,
+    ) -> *const rcl_guard_condition_t {
+        unsafe { self::rcl_node_get_graph_guard_condition(node) }
+    }
+
+    pub fn rcl_service_server_is_available(
+        &self,
+        node: *const rcl_node_t,
+        client: *const rcl_client_t,
+        is_available: *mut bool,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_service_server_is_available(node, client, is_available) })
+    }
+
+    pub fn rcl_count_publishers(
+        &self,
+        node: *const rcl_node_t,
+        topic_name: *const ::std::os::raw::c_char,
+        count: *mut size_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_count_publishers(node, topic_name, count) })
+    }
+
+    pub fn rcl_count_subscribers(
+        &self,
+        node: *const rcl_node_t,
+        topic_name: *const ::std::os::raw::c_char,
+        count: *mut size_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_count_subscribers(node, topic_name, count) })
+    }
+
+    pub fn rcl_get_publishers_info_by_topic(
+        &self,
+        node: *const rcl_node_t,
+        allocator: *mut rcutils_allocator_t,
+        topic_name: *const ::std::os::raw::c_char,
+        no_mangle: bool,
+        publishers_info: *mut rcl_topic_endpoint_info_array_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_get_publishers_info_by_topic(
+                node,
+                allocator,
+                topic_name,
+                no_mangle,
+                publishers_info,
+            )
+        })
+    }
+
+    pub fn rcl_get_subscriptions_info_by_topic(
+        &self,
+        node: *const rcl_node_t,
+        allocator: *mut rcutils_allocator_t,
+        topic_name: *const ::std::os::raw::c_char,
+        no_mangle: bool,
+        subscriptions_info: *mut rcl_topic_endpoint_info_array_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_get_subscriptions_info_by_topic(
+                node,
+                allocator,
+                topic_name,
+                no_mangle,
+                subscriptions_info,
+            )
+        })
+    }
+
+    pub fn rmw_topic_endpoint_info_array_fini(
+        &self,
+        topic_endpoint_info_array: *mut rmw_topic_endpoint_info_array_t,
+        allocator: *mut rcutils_allocator_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rmw_topic_endpoint_info_array_fini(topic_endpoint_info_array, allocator)
+        })
+    }
+
     pub fn rcutils_reset_error(&self) {
         unsafe { self::rcutils_reset_error() };
     }
@@ -809,6 +1378,25 @@ impl MTSafeFn {
         unsafe { self::rcl_node_get_default_options() }
     }
 
+    pub fn rcl_get_zero_initialized_arguments() -> rcl_arguments_t {
+        unsafe { self::rcl_get_zero_initialized_arguments() }
+    }
+
+    pub fn rcl_parse_arguments(
+        argc: ::std::os::raw::c_int,
+        argv: *const *const ::std::os::raw::c_char,
+        allocator: rcl_allocator_t,
+        args_output: *mut rcl_arguments_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_parse_arguments(argc, argv, allocator, args_output) })
+    }
+
+    pub fn rcl_arguments_get_count_unparsed_ros(
+        args: *const rcl_arguments_t,
+    ) -> ::std::os::raw::c_int {
+        unsafe { self::rcl_arguments_get_count_unparsed_ros(args) }
+    }
+
     pub fn rcl_get_zero_initialized_publisher() -> rcl_publisher_t {
         unsafe { self::rcl_get_zero_initialized_publisher() }
     }
@@ -821,6 +1409,17 @@ impl MTSafeFn {
         unsafe { self::rcl_subscription_can_loan_messages(subscription) }
     }
 
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub fn rcl_get_zero_initialized_subscription_content_filter_options(
+    ) -> rcl_subscription_content_filter_options_t {
+        unsafe { self::rcl_get_zero_initialized_subscription_content_filter_options() }
+    }
+
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub fn rcl_subscription_is_cft_enabled(subscription: *const rcl_subscription_t) -> bool {
+        unsafe { self::rcl_subscription_is_cft_enabled(subscription) }
+    }
+
     pub fn rcl_publish(
         publisher: *const rcl_publisher_t,
         ros_message: *const ::std::os::raw::c_void,
@@ -829,6 +1428,16 @@ impl MTSafeFn {
         ret_val_to_err(unsafe { self::rcl_publish(publisher, ros_message, allocation) })
     }
 
+    pub fn rcl_publish_serialized_message(
+        publisher: *const rcl_publisher_t,
+        serialized_message: *const rcl_serialized_message_t,
+        allocation: *mut rmw_publisher_allocation_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_publish_serialized_message(publisher, serialized_message, allocation)
+        })
+    }
+
     pub fn rcl_publish_loaned_message(
         publisher: *const rcl_publisher_t,
         ros_message: *mut ::std::os::raw::c_void,
@@ -985,4 +1594,116 @@ impl MTSafeFn {
     pub fn rcl_action_get_zero_initialized_cancel_response() -> rcl_action_cancel_response_t {
         unsafe { self::rcl_action_get_zero_initialized_cancel_response() }
     }
+
+    pub fn rmw_get_zero_initialized_names_and_types() -> rmw_names_and_types_t {
+        unsafe { self::rmw_get_zero_initialized_names_and_types() }
+    }
+
+    pub fn rcl_get_zero_initialized_event() -> rcl_event_t {
+        unsafe { self::rcl_get_zero_initialized_event() }
+    }
+
+    pub fn rmw_get_zero_initialized_message_info() -> rmw_message_info_t {
+        unsafe { self::rmw_get_zero_initialized_message_info() }
+    }
+
+    pub fn rmw_qos_profile_check_compatible(
+        publisher_profile: rmw_qos_profile_t,
+        subscription_profile: rmw_qos_profile_t,
+        compatibility: *mut rmw_qos_compatibility_type_t,
+        reason: *mut ::std::os::raw::c_char,
+        reason_size: size_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rmw_qos_profile_check_compatible(
+                publisher_profile,
+                subscription_profile,
+                compatibility,
+                reason,
+                reason_size,
+            )
+        })
+    }
+
+    pub fn rmw_get_zero_initialized_message_sequence() -> rmw_message_sequence_t {
+        unsafe { self::rmw_get_zero_initialized_message_sequence() }
+    }
+
+    pub fn rmw_get_zero_initialized_message_info_sequence() -> rmw_message_info_sequence_t {
+        unsafe { self::rmw_get_zero_initialized_message_info_sequence() }
+    }
+
+    pub fn rcutils_get_zero_initialized_uint8_array() -> rcutils_uint8_array_t {
+        unsafe { self::rcutils_get_zero_initialized_uint8_array() }
+    }
+
+    pub fn rcutils_uint8_array_init(
+        uint8_array: *mut rcutils_uint8_array_t,
+        buffer_capacity: size_t,
+        allocator: *const rcutils_allocator_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcutils_uint8_array_init(uint8_array, buffer_capacity, allocator)
+        })
+    }
+
+    pub fn rcutils_uint8_array_fini(uint8_array: *mut rcutils_uint8_array_t) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcutils_uint8_array_fini(uint8_array) })
+    }
+
+    pub fn rcutils_uint8_array_resize(
+        uint8_array: *mut rcutils_uint8_array_t,
+        new_size: size_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcutils_uint8_array_resize(uint8_array, new_size) })
+    }
+
+    pub fn rcutils_get_zero_initialized_string_array() -> rcutils_string_array_t {
+        unsafe { self::rcutils_get_zero_initialized_string_array() }
+    }
+
+    pub fn rmw_get_zero_initialized_topic_endpoint_info_array() -> rmw_topic_endpoint_info_array_t {
+        unsafe { self::rmw_get_zero_initialized_topic_endpoint_info_array() }
+    }
+
+    pub fn rcl_publisher_assert_liveliness(publisher: *const rcl_publisher_t) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_publisher_assert_liveliness(publisher) })
+    }
+
+    #[cfg(any(feature = "humble", feature = "iron"))]
+    pub fn rcl_publisher_wait_for_all_acked(
+        publisher: *const rcl_publisher_t,
+        timeout: rcl_duration_value_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_publisher_wait_for_all_acked(publisher, timeout) })
+    }
+
+    pub fn rcl_publisher_get_rmw_handle(publisher: *const rcl_publisher_t) -> *mut rmw_publisher_t {
+        unsafe { self::rcl_publisher_get_rmw_handle(publisher) }
+    }
+
+    pub fn rmw_get_gid_for_publisher(
+        publisher: *const rmw_publisher_t,
+        gid: *mut rmw_gid_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rmw_get_gid_for_publisher(publisher, gid) })
+    }
+
+    pub fn rcl_publisher_get_subscription_count(
+        publisher: *const rcl_publisher_t,
+        subscription_count: *mut size_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_publisher_get_subscription_count(publisher, subscription_count)
+        })
+    }
+
+    pub fn rcl_subscription_get_publisher_count(
+        subscription: *const rcl_subscription_t,
+        publisher_count: *mut size_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_subscription_get_publisher_count(subscription, publisher_count)
+        })
+    }
 }
@@ -58,7 +58,7 @@
 use self::guard_condition::{GuardCondition, RCLGuardCondition};
 use crate::{
     action::{self, handle::GoalHandle, update_goal_status, GoalStatus, SendGoalServiceRequest},
-    context::Context,
+    context::{Context, ContextShutdown},
     delta_list::DeltaList,
     error::{DynError, RCLActionResult, RCLError, RCLResult},
     get_allocator,
@@ -70,18 +70,22 @@ use crate::{
         },
         ActionMsg, GetUUID, ServiceMsg, TypeSupport,
     },
+    node::Node,
     parameter::{ParameterServer, Parameters},
     rcl::{
         self, action_msgs__msg__GoalInfo, action_msgs__msg__GoalInfo__Sequence,
         rcl_action_client_t, rcl_action_server_t,
     },
     service::{
-        client::{ClientData, ClientRecv},
-        server::{Server, ServerData},
+        client::{CallbackClient, Client, ClientData, ClientRecv},
+        server::{Responder, Server, ServerData},
         Header,
     },
     signal_handler::{self, Signaled},
-    topic::subscriber::{RCLSubscription, Subscriber, TakenMsg},
+    topic::{
+        event::{Event, EventStatus, RCLEvent},
+        subscriber::{MessageInfo, RCLSubscription, Subscriber, TakenMsg},
+    },
     PhantomUnsend, PhantomUnsync, RecvResult, ST,
 };
 use std::{
@@ -106,6 +110,10 @@ pub(crate) mod guard_condition;
 
 type ServerCallback<T> =
     Box<dyn FnMut(<T as ServiceMsg>::Request, Header) -> <T as ServiceMsg>::Response>;
+type DeferredServerCallback<T> = Box<dyn FnMut(<T as ServiceMsg>::Request, Header, Responder<T>)>;
+/// Timers requested where the selector cannot be borrowed, such as in callback functions.
+/// They are added when waiting next time.
+pub(crate) type TimerRequests = Rc<RefCell<Vec<(SystemTime, Box<dyn FnMut()>)>>>;
 type ParameterCallback = Box<dyn FnMut(&mut Parameters, BTreeSet<String>)>;
 
 #[derive(Debug, Eq, PartialEq)]
@@ -201,8 +209,11 @@ pub struct Selector {
     action_servers: BTreeMap<*const rcl::rcl_action_server_t, ActionServerConditionHandler>,
     action_clients: BTreeMap<*const rcl::rcl_action_client_t, ActionClientConditionHandler>,
     cond: BTreeMap<*const rcl::rcl_guard_condition_t, ConditionHandler<Arc<RCLGuardCondition>>>,
+    graph_conds: BTreeMap<*const rcl::rcl_node_t, GuardCondition>,
+    events: BTreeMap<*const rcl::rcl_event_t, ConditionHandler<Arc<RCLEvent>>>,
     timer_ids: BTreeSet<u64>,
     timer_id: u64,
+    timer_requests: TimerRequests,
     context: Arc<Context>,
 
     #[cfg(feature = "statistics")]
@@ -252,8 +263,11 @@ impl Selector {
             action_servers: Default::default(),
             action_clients: Default::default(),
             cond: Default::default(),
+            graph_conds: Default::default(),
+            events: Default::default(),
             timer_ids: Default::default(),
             timer_id: 0,
+            timer_requests: Default::default(),
 
             context,
 
@@ -264,6 +278,7 @@ impl Selector {
         };
 
         selector.add_guard_condition(&signal_cond, None, false);
+        selector.context.register_wakeup_cond(&signal_cond.cond);
         signal_handler::register_guard_condition(signal_cond);
 
         Ok(selector)
@@ -340,6 +355,125 @@ impl Selector {
         subscriber: Subscriber<T>,
         mut handler: Box<dyn FnMut(TakenMsg<T>)>,
     ) -> bool {
+        self.add_subscriber_recv(subscriber, move |s| match s.try_recv() {
+            RecvResult::Ok(n) => {
+                handler(n);
+                RecvResult::Ok(())
+            }
+            RecvResult::RetryLater(()) => RecvResult::RetryLater(()),
+            RecvResult::Err(e) => RecvResult::Err(e),
+        })
+    }
+
+    /// Register a subscriber with callback function taking information of messages.
+    /// The callback function will be invoked when arriving data
+    /// with `MessageInfo`, which includes timestamps and the GID of the publisher.
+    ///
+    /// # Error
+    ///
+    /// If a selector takes a subscriber created by a different context,
+    /// `add_subscriber_with_info()` must fail.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{
+    ///     msg::common_interfaces::std_msgs,
+    ///     node::Node,
+    ///     selector::Selector,
+    ///     topic::subscriber::{MessageInfo, TakenMsg},
+    /// };
+    /// use std::sync::Arc;
+    ///
+    /// fn add_new_subscriber(selector: &mut Selector, node: Arc<Node>) {
+    ///     // Create a subscriber.
+    ///     let subscriber = node.create_subscriber("node_name", None,
+    ///         #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    ///         true
+    ///     ).unwrap();
+    ///
+    ///     // Add the subscriber with a callback function.
+    ///     selector.add_subscriber_with_info(
+    ///         subscriber,
+    ///         Box::new(|msg: TakenMsg<std_msgs::msg::Bool>, info: MessageInfo| {
+    ///             let latency = info.received_timestamp - info.source_timestamp;
+    ///             println!("data = {}, latency = {latency}[ns]", msg.data);
+    ///         }),
+    ///     );
+    /// }
+    /// ```
+    pub fn add_subscriber_with_info<T: TypeSupport + 'static>(
+        &mut self,
+        subscriber: Subscriber<T>,
+        mut handler: Box<dyn FnMut(TakenMsg<T>, MessageInfo)>,
+    ) -> bool {
+        self.add_subscriber_recv(subscriber, move |s| match s.try_recv_with_info() {
+            RecvResult::Ok((n, info)) => {
+                handler(n, info);
+                RecvResult::Ok(())
+            }
+            RecvResult::RetryLater(()) => RecvResult::RetryLater(()),
+            RecvResult::Err(e) => RecvResult::Err(e),
+        })
+    }
+
+    /// Register a subscriber with callback function taking a batch of messages.
+    /// The callback function will be invoked with at most `max` messages
+    /// taken at once by `Subscriber::try_recv_many`.
+    ///
+    /// This reduces the overhead of waiting and taking messages of high-rate topics.
+    ///
+    /// # Error
+    ///
+    /// If a selector takes a subscriber created by a different context,
+    /// `add_subscriber_batch()` must fail.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{msg::common_interfaces::std_msgs, node::Node, selector::Selector, topic::subscriber::TakenMsg};
+    /// use std::sync::Arc;
+    ///
+    /// fn add_new_subscriber(selector: &mut Selector, node: Arc<Node>) {
+    ///     // Create a subscriber.
+    ///     let subscriber = node.create_subscriber("node_name", None,
+    ///         #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    ///         true
+    ///     ).unwrap();
+    ///
+    ///     // Add the subscriber with a callback function taking at most 32 messages.
+    ///     selector.add_subscriber_batch(
+    ///         subscriber,
+    ///         32,
+    ///         Box::new(|msgs: Vec<TakenMsg<std_msgs::msg::Float64>>| {
+    ///             let sum: f64 = msgs.iter().map(|msg| msg.data).sum();
+    ///             println!("average = {}", sum / msgs.len() as f64);
+    ///         }),
+    ///     );
+    /// }
+    /// ```
+    pub fn add_subscriber_batch<T: TypeSupport + 'static>(
+        &mut self,
+        subscriber: Subscriber<T>,
+        max: usize,
+        mut handler: Box<dyn FnMut(Vec<TakenMsg<T>>)>,
+    ) -> bool {
+        self.add_subscriber_recv(subscriber, move |s| match s.try_recv_many(max) {
+            RecvResult::Ok(msgs) => {
+                handler(msgs);
+                RecvResult::Ok(())
+            }
+            RecvResult::RetryLater(()) => RecvResult::RetryLater(()),
+            RecvResult::Err(e) => RecvResult::Err(e),
+        })
+    }
+
+    /// Register a subscriber whose messages are received and handled by `recv`.
+    fn add_subscriber_recv<T, F>(&mut self, subscriber: Subscriber<T>, mut recv: F) -> bool
+    where
+        T: TypeSupport + 'static,
+        F: FnMut(&Subscriber<T>) -> RecvResult<(), ()> + 'static,
+    {
         let sub = subscriber.subscription.clone();
         let context_ptr = subscriber.subscription.node.context.as_ptr();
 
@@ -361,10 +495,8 @@ impl Selector {
             let dur = Duration::from_millis(1);
 
             loop {
-                match subscriber.try_recv() {
-                    RecvResult::Ok(n) => {
-                        handler(n);
-                    }
+                match recv(&subscriber) {
+                    RecvResult::Ok(()) => (),
                     RecvResult::RetryLater(()) => return CallbackResult::Ok,
                     RecvResult::Err(e) => {
                         let logger = Logger::new("safe_drive");
@@ -405,6 +537,24 @@ impl Selector {
         handler: Option<Box<dyn FnMut() -> CallbackResult>>,
         is_once: bool,
     ) {
+        // Messages of intra-process publishers are notified by the guard condition of the queue,
+        // so the handler is shared with the guard condition.
+        let handler = match (&subscription.intra_process, handler) {
+            (Some(queue), Some(handler)) => {
+                let handler = Rc::new(RefCell::new(handler));
+                let is_done = Rc::new(Cell::new(false));
+
+                self.add_guard_condition(
+                    &queue.cond,
+                    Some(share_handler(handler.clone(), is_done.clone(), is_once)),
+                    is_once,
+                );
+
+                Some(share_handler(handler, is_done, is_once))
+            }
+            (_, handler) => handler,
+        };
+
         self.subscriptions.insert(
             subscription.subscription.as_ref(),
             ConditionHandler {
@@ -528,6 +678,103 @@ impl Selector {
         }
     }
 
+    /// Register a server with a callback function which responds later.
+    /// The callback function will be invoked when arriving requests.
+    ///
+    /// The callback function takes `ServiceMsg::Request`, `Header`, and `Responder`.
+    /// `Responder` can be stored and used to send the response later,
+    /// for example, in a timer or subscriber callback.
+    /// The server keeps receiving requests while responses are deferred.
+    ///
+    /// # Error
+    ///
+    /// If a selector takes a server created by a different context,
+    /// `add_server_deferred()` must fail.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{
+    ///     msg::common_interfaces::std_srvs, node::Node, selector::Selector,
+    ///     service::server::Responder,
+    /// };
+    /// use std::{cell::RefCell, rc::Rc, sync::Arc, time::Duration};
+    ///
+    /// fn add_deferred_server(selector: &mut Selector, node: Arc<Node>) {
+    ///     // Create a server.
+    ///     let server = node
+    ///         .create_server::<std_srvs::srv::Empty>("select_rs_deferred_service", None)
+    ///         .unwrap();
+    ///
+    ///     // Responders waiting for responses.
+    ///     let responders: Rc<RefCell<Vec<Responder<std_srvs::srv::Empty>>>> = Default::default();
+    ///     let responders_cloned = responders.clone();
+    ///
+    ///     // Store responders.
+    ///     selector.add_server_deferred(
+    ///         server,
+    ///         Box::new(move |_request, _header, responder| {
+    ///             responders_cloned.borrow_mut().push(responder);
+    ///         }),
+    ///     );
+    ///
+    ///     // Respond periodically.
+    ///     selector.add_wall_timer(
+    ///         "deferred_responder",
+    ///         Duration::from_millis(100),
+    ///         Box::new(move || {
+    ///             for responder in responders.borrow_mut().drain(..) {
+    ///                 let response = std_srvs::srv::EmptyResponse::new().unwrap();
+    ///                 let _ = responder.send(&response);
+    ///             }
+    ///         }),
+    ///     );
+    /// }
+    /// ```
+    pub fn add_server_deferred<T: ServiceMsg + 'static>(
+        &mut self,
+        server: Server<T>,
+        mut handler: DeferredServerCallback<T>,
+    ) -> bool {
+        let context_ptr = server.data.node.context.as_ptr();
+        let srv = server.data.clone();
+        let mut server = server.into_concurrent();
+
+        let f = move || {
+            let start = SystemTime::now();
+            let dur = Duration::from_millis(1);
+
+            loop {
+                match server.try_recv() {
+                    RecvResult::Ok((responder, request, header)) => {
+                        handler(request, header, responder);
+                    }
+                    RecvResult::RetryLater(_) => return CallbackResult::Ok,
+                    RecvResult::Err(e) => {
+                        let logger = Logger::new("safe_drive");
+                        pr_fatal_in!(logger, "failed try_recv() of server: {}", e);
+                        return CallbackResult::Remove;
+                    }
+                }
+
+                if let Ok(t) = start.elapsed() {
+                    if t > dur {
+                        return CallbackResult::Ok;
+                    }
+                } else {
+                    return CallbackResult::Ok;
+                }
+            }
+        };
+
+        if self.context.as_ptr() == context_ptr {
+            self.add_server_data(srv, Some(Box::new(f)), false);
+            true
+        } else {
+            false
+        }
+    }
+
     pub(crate) fn add_server_data(
         &mut self,
         server: Arc<ServerData>,
@@ -536,7 +783,7 @@ impl Selector {
     ) {
         if self.context.as_ptr() == server.node.context.as_ptr() {
             self.services.insert(
-                &server.service,
+                server.as_ptr(),
                 ConditionHandler {
                     event: server,
                     handler,
@@ -546,6 +793,28 @@ impl Selector {
         }
     }
 
+    /// Register a client whose responses are handled by callback functions.
+    /// Requests are sent by `CallbackClient::call_with_callback`,
+    /// which can be called in callback functions of the selector.
+    ///
+    /// If a selector takes a client created by a different context,
+    /// this returns `None`.
+    ///
+    /// See `service::client::CallbackClient`.
+    pub fn add_client<T: ServiceMsg + 'static>(
+        &mut self,
+        client: Client<T>,
+    ) -> Option<CallbackClient<T>> {
+        if self.context.as_ptr() != client.data.node.context.as_ptr() {
+            return None;
+        }
+
+        let client = CallbackClient::new(client, self.timer_requests.clone());
+        self.add_client_data(client.data(), Some(client.handler()), false);
+
+        Some(client)
+    }
+
     /// Wait a response from a server.
     /// After waking up, the registered client is removed from the selector.
     /// You have to register every time when you wait events.
@@ -560,7 +829,7 @@ impl Selector {
         is_once: bool,
     ) {
         self.clients.insert(
-            &client.client,
+            client.as_ptr(),
             ConditionHandler {
                 event: client,
                 handler,
@@ -915,14 +1184,157 @@ impl Selector {
     pub(crate) fn remove_rcl_subscription(&mut self, subscription: &Arc<RCLSubscription>) {
         self.subscriptions
             .remove(&(subscription.subscription.as_ref() as *const _));
+
+        if let Some(queue) = &subscription.intra_process {
+            self.remove_guard_condition(&queue.cond);
+        }
     }
 
     pub(crate) fn remove_server_data(&mut self, server: &Arc<ServerData>) {
-        self.services.remove(&(&server.service as *const _));
+        self.services.remove(&server.as_ptr());
     }
 
     pub(crate) fn remove_client_data(&mut self, client: &Arc<ClientData>) {
-        self.clients.remove(&(&client.client as *const _));
+        self.clients.remove(&client.as_ptr());
+    }
+
+    /// Register a callback function invoked when the ROS graph seen by `node` changes.
+    /// The ROS graph changes when nodes, publishers, subscribers, servers, or clients
+    /// appear or disappear, or when publishers and subscribers are matched.
+    /// If a handler of `node` has been already registered, it is replaced.
+    ///
+    /// # Error
+    ///
+    /// If a selector takes a node created by a different context,
+    /// `add_graph_event_handler()` must fail and return `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{node::Node, selector::Selector};
+    /// use std::sync::Arc;
+    ///
+    /// fn add_graph_event_handler(selector: &mut Selector, node: Arc<Node>) {
+    ///     let n = node.clone();
+    ///     selector.add_graph_event_handler(
+    ///         &node,
+    ///         Box::new(move || {
+    ///             // Check the current graph.
+    ///             let nodes = n.get_node_names_and_namespaces().unwrap();
+    ///             println!("nodes: {nodes:?}");
+    ///         }),
+    ///     );
+    /// }
+    /// ```
+    pub fn add_graph_event_handler(
+        &mut self,
+        node: &Arc<Node>,
+        mut handler: Box<dyn FnMut()>,
+    ) -> bool {
+        if self.context.as_ptr() != node.context.as_ptr() {
+            return false;
+        }
+
+        let Ok(cond) = GuardCondition::new_graph(node.clone()) else {
+            return false;
+        };
+
+        self.remove_graph_event_handler(node);
+
+        self.add_guard_condition(
+            &cond,
+            Some(Box::new(move || {
+                handler();
+                CallbackResult::Ok
+            })),
+            false,
+        );
+        self.graph_conds.insert(node.as_ptr(), cond);
+
+        true
+    }
+
+    /// Unregister the callback function registered by `add_graph_event_handler`.
+    pub fn remove_graph_event_handler(&mut self, node: &Arc<Node>) {
+        if let Some(cond) = self.graph_conds.remove(&node.as_ptr()) {
+            self.remove_guard_condition(&cond);
+        }
+    }
+
+    /// Register an event of a publisher or a subscriber with a callback function.
+    /// The `handler` is called with the status of the event when the event occurs.
+    ///
+    /// # Error
+    ///
+    /// If a selector takes an event created by a different context,
+    /// `add_event_handler()` must fail and return `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{
+    ///     selector::Selector,
+    ///     topic::event::{Event, EventStatus},
+    /// };
+    ///
+    /// fn add_event_handler(selector: &mut Selector, event: Event) {
+    ///     selector.add_event_handler(
+    ///         event,
+    ///         Box::new(|status| {
+    ///             if let EventStatus::RequestedIncompatibleQoS(status) = status {
+    ///                 println!("incompatible QoS: {:?}", status.last_policy_kind);
+    ///             }
+    ///         }),
+    ///     );
+    /// }
+    /// ```
+    pub fn add_event_handler(
+        &mut self,
+        event: Event,
+        mut handler: Box<dyn FnMut(EventStatus)>,
+    ) -> bool {
+        if self.context.as_ptr() != event.event.node.context.as_ptr() {
+            return false;
+        }
+
+        let rcl_event = event.event.clone();
+        self.add_rcl_event(
+            event.event,
+            Box::new(move || {
+                match rcl_event.take() {
+                    Ok(status) => handler(status),
+                    Err(RCLError::EventTakeFailed) => (),
+                    Err(e) => {
+                        let logger = Logger::new("safe_drive");
+                        pr_error_in!(logger, "failed to take an event: {e}");
+                    }
+                }
+                CallbackResult::Ok
+            }),
+            false,
+        );
+
+        true
+    }
+
+    pub(crate) fn add_rcl_event(
+        &mut self,
+        event: Arc<RCLEvent>,
+        handler: Box<dyn FnMut() -> CallbackResult>,
+        is_once: bool,
+    ) {
+        self.events.insert(
+            event.event.as_ref(),
+            ConditionHandler {
+                event,
+                handler: Some(handler),
+                is_once,
+            },
+        );
+    }
+
+    pub(crate) fn remove_rcl_event(&mut self, event: &Arc<RCLEvent>) {
+        self.events.remove(&(event.event.as_ref() as *const _));
     }
 
     /// Add a timer.
@@ -1092,6 +1504,7 @@ impl Selector {
 
     pub fn remove_timer(&mut self, id: u64) {
         self.timer.filter(|e| e.1 != id);
+        self.timer_ids.remove(&id);
     }
 
     fn new_timer_id(&mut self) -> u64 {
@@ -1122,6 +1535,14 @@ impl Selector {
     /// }
     /// ```
     pub fn wait(&mut self) -> Result<(), DynError> {
+        // add timers requested in callback functions
+        let requests: Vec<_> = self.timer_requests.borrow_mut().drain(..).collect();
+        let now = SystemTime::now();
+        for (deadline, handler) in requests {
+            let t = deadline.duration_since(now).unwrap_or_default();
+            self.add_timer(t, handler);
+        }
+
         {
             let guard = rcl::MT_UNSAFE_FN.lock();
             guard.rcl_wait_set_clear(&mut self.wait_set)?;
@@ -1157,12 +1578,21 @@ impl Selector {
 
             // set clients
             for (_, h) in self.clients.iter() {
-                guard.rcl_wait_set_add_client(&mut self.wait_set, &h.event.client, null_mut())?;
+                guard.rcl_wait_set_add_client(&mut self.wait_set, h.event.as_ptr(), null_mut())?;
             }
 
             // set services
             for (_, h) in self.services.iter() {
-                guard.rcl_wait_set_add_service(&mut self.wait_set, &h.event.service, null_mut())?;
+                guard.rcl_wait_set_add_service(&mut self.wait_set, h.event.as_ptr(), null_mut())?;
+            }
+
+            // set events
+            for (_, h) in self.events.iter() {
+                guard.rcl_wait_set_add_event(
+                    &mut self.wait_set,
+                    h.event.event.as_ref(),
+                    null_mut(),
+                )?;
             }
 
             // set action clients
@@ -1208,6 +1638,10 @@ impl Selector {
             // notify guard conditions
             let (target, time_stat) = (&mut self.cond, &mut self.time_stat);
             notify(target, self.wait_set.guard_conditions, time_stat);
+
+            // notify events
+            let (target, time_stat) = (&mut self.events, &mut self.time_stat);
+            notify(target, self.wait_set.events, time_stat);
         }
 
         #[cfg(not(feature = "statistics"))]
@@ -1224,6 +1658,9 @@ impl Selector {
             // notify guard conditions
             notify(&mut self.cond, self.wait_set.guard_conditions);
 
+            // notify events
+            notify(&mut self.events, self.wait_set.events);
+
             notify_action_server(&mut self.action_servers, &self.wait_set)?;
             notify_action_client(&mut self.action_clients, &self.wait_set)?;
         }
@@ -1236,6 +1673,10 @@ impl Selector {
             return Err(Signaled.into());
         }
 
+        if !self.context.is_valid() {
+            return Err(ContextShutdown.into());
+        }
+
         if self.timer.is_empty() {
             #[cfg(feature = "rcl_stat")]
             let wait_start = SystemTime::now();
@@ -1297,6 +1738,10 @@ impl Selector {
             return Err(Signaled.into());
         }
 
+        if !self.context.is_valid() {
+            return Err(ContextShutdown.into());
+        }
+
         Ok(())
     }
 
@@ -1406,7 +1851,7 @@ impl Selector {
             services: self.services.len() as rcl::size_t
                 + action_server_services_size * n_servers
                 + action_client_services_size * n_clients,
-            events: 0,
+            events: self.events.len() as rcl::size_t,
         })
     }
 }
@@ -1414,6 +1859,7 @@ impl Selector {
 impl Drop for Selector {
     fn drop(&mut self) {
         signal_handler::unregister_guard_condition(&self.signal_cond);
+        self.context.unregister_wakeup_cond(&self.signal_cond.cond);
         {
             let guard = rcl::MT_UNSAFE_FN.lock();
             guard.rcl_wait_set_fini(&mut self.wait_set).unwrap();
@@ -1421,6 +1867,28 @@ impl Drop for Selector {
     }
 }
 
+/// Share `handler` between two conditions.
+/// Once `handler` returns `CallbackResult::Remove`, or is invoked if `is_once`,
+/// the other condition is removed without invoking `handler` when it is notified.
+fn share_handler(
+    handler: Rc<RefCell<Box<dyn FnMut() -> CallbackResult>>>,
+    is_done: Rc<Cell<bool>>,
+    is_once: bool,
+) -> Box<dyn FnMut() -> CallbackResult> {
+    Box::new(move || {
+        if is_done.get() {
+            return CallbackResult::Remove;
+        }
+
+        let result = (handler.borrow_mut())();
+        if is_once || result == CallbackResult::Remove {
+            is_done.set(true);
+        }
+
+        result
+    })
+}
+
 #[cfg(feature = "statistics")]
 fn notify<K, V>(
     m: &mut BTreeMap<*const K, ConditionHandler<V>>,
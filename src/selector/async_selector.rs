@@ -1,19 +1,28 @@
-use super::{guard_condition::GuardCondition, CallbackResult};
+use super::{
+    guard_condition::{GuardCondition, RCLGuardCondition},
+    CallbackResult,
+};
 use crate::{
-    context::Context,
+    context::{Context, ContextShutdown},
     error::DynError,
+    rcl,
     service::{client::ClientData, server::ServerData},
     signal_handler,
-    topic::subscriber::RCLSubscription,
+    topic::{event::RCLEvent, subscriber::RCLSubscription},
 };
-use crossbeam_channel::{self, Receiver, Sender};
+use crossbeam_channel::{self, Receiver, SendError, Sender};
 use once_cell::sync::Lazy;
 use parking_lot::Mutex;
 use std::{
-    sync::Arc,
+    collections::BTreeMap,
+    sync::{Arc, Weak},
     thread::{self, yield_now, JoinHandle},
+    time::Duration,
 };
 
+/// A selector thread exits if nothing is registered for this duration.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(1);
+
 pub(crate) static SELECTOR: Lazy<Mutex<AsyncSelector>> =
     Lazy::new(|| Mutex::new(AsyncSelector::new()));
 
@@ -38,18 +47,38 @@ pub(crate) enum Command {
         Box<dyn FnMut() -> CallbackResult + Send + Sync + 'static>,
     ),
     RemoveConditionVar(GuardCondition),
+    Event(
+        Arc<RCLEvent>,
+        Box<dyn FnMut() -> CallbackResult + Send + Sync + 'static>,
+    ),
+    RemoveEvent(Arc<RCLEvent>),
     Halt,
 }
 
+/// The thread holds references to the context while it is running,
+/// so only weak references are held here.
 struct SelectorData {
     tx: Sender<Command>,
     th: JoinHandle<Result<(), DynError>>,
-    cond: GuardCondition,
-    _context: Arc<Context>,
+    cond: Weak<RCLGuardCondition>,
+
+    // `true` if the thread has exited, or no longer receives commands.
+    is_exited: Arc<Mutex<bool>>,
+}
+
+/// Wake the selector thread up, if it is still running.
+fn trigger(cond: &Weak<RCLGuardCondition>) -> Result<(), DynError> {
+    if let Some(cond) = cond.upgrade() {
+        GuardCondition { cond }.trigger()?;
+    }
+    Ok(())
 }
 
+/// Selectors for asynchronous execution.
+/// A selector thread is spawned for each context,
+/// because a wait set can contain only entities created by the same context.
 pub(crate) struct AsyncSelector {
-    data: Option<SelectorData>,
+    data: BTreeMap<*const rcl::rcl_context_t, SelectorData>,
 }
 
 unsafe impl Sync for AsyncSelector {}
@@ -57,13 +86,34 @@ unsafe impl Send for AsyncSelector {}
 
 impl AsyncSelector {
     fn new() -> Self {
-        AsyncSelector { data: None }
+        AsyncSelector {
+            data: BTreeMap::new(),
+        }
     }
 
     pub(crate) fn halt(&mut self) -> Result<(), DynError> {
-        if let Some(SelectorData { tx, cond, th, .. }) = self.data.take() {
-            tx.send(Command::Halt)?;
-            cond.trigger()?;
+        let data = std::mem::take(&mut self.data);
+        for (_, SelectorData { tx, cond, th, .. }) in data {
+            // the thread may have already exited
+            let _ = tx.send(Command::Halt);
+            let _ = trigger(&cond);
+
+            yield_now();
+            let _ = th.join();
+        }
+
+        Ok(())
+    }
+
+    /// Halt the selector thread of `context`, if any.
+    pub(crate) fn halt_context(
+        &mut self,
+        context: *const rcl::rcl_context_t,
+    ) -> Result<(), DynError> {
+        if let Some(SelectorData { tx, cond, th, .. }) = self.data.remove(&context) {
+            // the thread may have already exited
+            let _ = tx.send(Command::Halt);
+            let _ = trigger(&cond);
 
             yield_now();
             let _ = th.join();
@@ -75,30 +125,57 @@ impl AsyncSelector {
     pub(crate) fn send_command(
         &mut self,
         context: &Arc<Context>,
-        cmd: Command,
+        mut cmd: Command,
     ) -> Result<(), DynError> {
+        if !context.is_valid() {
+            return Err(ContextShutdown.into());
+        }
+
         loop {
-            if let Some(SelectorData { tx, cond, .. }) = &self.data {
-                tx.send(cmd)?;
-                cond.trigger()?;
-                return Ok(());
-            } else {
-                if let Command::Halt = cmd {
-                    return Ok(());
+            if let Some(SelectorData {
+                tx,
+                cond,
+                is_exited,
+                ..
+            }) = self.data.get(&context.as_ptr())
+            {
+                let is_exited = is_exited.lock();
+                if !*is_exited {
+                    match tx.send(cmd) {
+                        Ok(_) => {
+                            drop(is_exited);
+                            trigger(cond)?;
+                            return Ok(());
+                        }
+                        // the thread is exiting
+                        Err(SendError(c)) => cmd = c,
+                    }
                 }
+            }
+
+            if let Command::Halt = cmd {
+                return Ok(());
+            }
+
+            // remove exited threads, which are detached
+            self.data.retain(|_, data| !*data.is_exited.lock());
 
-                let (tx, rx) = crossbeam_channel::bounded(256);
-                let guard = super::guard_condition::GuardCondition::new(context.clone())?;
-                let ctx = context.clone();
-                let guard2 = guard.clone();
-                let th = thread::spawn(move || select(ctx, guard2, rx));
-                self.data = Some(SelectorData {
+            let (tx, rx) = crossbeam_channel::bounded(256);
+            let guard = GuardCondition::new(context.clone())?;
+            let cond = Arc::downgrade(&guard.cond);
+            let ctx = context.clone();
+            let is_exited = Arc::new(Mutex::new(false));
+            let is_exited2 = is_exited.clone();
+            let th = thread::spawn(move || select(ctx, guard, rx, is_exited2));
+            self.data.insert(
+                context.as_ptr(),
+                SelectorData {
                     tx,
                     th,
-                    _context: context.clone(),
-                    cond: guard,
-                });
-            }
+                    cond,
+                    is_exited,
+                },
+            );
         }
     }
 }
@@ -107,6 +184,18 @@ fn select(
     context: Arc<Context>,
     guard: GuardCondition,
     rx: Receiver<Command>,
+    is_exited: Arc<Mutex<bool>>,
+) -> Result<(), DynError> {
+    let result = select_loop(context, guard, rx, &is_exited);
+    *is_exited.lock() = true;
+    result
+}
+
+fn select_loop(
+    context: Arc<Context>,
+    guard: GuardCondition,
+    rx: Receiver<Command>,
+    is_exited: &Mutex<bool>,
 ) -> Result<(), DynError> {
     let mut selector = super::Selector::new(context)?;
 
@@ -123,12 +212,33 @@ fn select(
                 Command::RemoveClient(c) => selector.remove_client_data(&c),
                 Command::ConditionVar(c, h) => selector.add_guard_condition(&c, Some(h), true),
                 Command::RemoveConditionVar(c) => selector.remove_guard_condition(&c),
+                Command::Event(e, h) => selector.add_rcl_event(e, h, false),
+                Command::RemoveEvent(e) => selector.remove_rcl_event(&e),
                 Command::Halt => return Ok(()),
             }
         }
 
-        if let Err(_e) = selector.wait() {
-            if signal_handler::is_halt() {
+        // Exit if nothing has been registered for a while,
+        // so that the thread does not keep the context alive.
+        let result = if is_idle(&selector) {
+            match selector.wait_timeout(IDLE_TIMEOUT) {
+                Ok(false) => {
+                    if let Some(mut is_exited) = is_exited.try_lock() {
+                        if rx.is_empty() {
+                            *is_exited = true;
+                            return Ok(());
+                        }
+                    }
+                    Ok(())
+                }
+                result => result.map(|_| ()),
+            }
+        } else {
+            selector.wait()
+        };
+
+        if let Err(_e) = result {
+            if signal_handler::is_halt() || !selector.context.is_valid() {
                 for (_, h) in selector.subscriptions.iter_mut() {
                     if let Some(handler) = &mut h.handler {
                         (*handler)();
@@ -153,8 +263,23 @@ fn select(
                     }
                 }
 
+                for (_, h) in selector.events.iter_mut() {
+                    if let Some(handler) = &mut h.handler {
+                        (*handler)();
+                    }
+                }
+
                 return Ok(());
             }
         }
     }
 }
+
+/// Check whether nothing but the guard condition to wake the thread up is registered.
+fn is_idle(selector: &super::Selector) -> bool {
+    selector.subscriptions.is_empty()
+        && selector.services.is_empty()
+        && selector.clients.is_empty()
+        && selector.events.is_empty()
+        && selector.cond.len() <= 1
+}
@@ -1,8 +1,18 @@
-use crate::{context::Context, error::RCLResult, get_allocator, rcl};
+use crate::{
+    context::Context,
+    error::{RCLError, RCLResult},
+    get_allocator,
+    node::Node,
+    rcl,
+};
 use std::sync::Arc;
 
 pub(crate) struct RCLGuardCondition {
     pub(crate) cond: Box<rcl::rcl_guard_condition_t>,
+
+    // `Some` if this is the graph guard condition owned by the node.
+    node: Option<Arc<Node>>,
+
     _context: Arc<Context>,
 }
 
@@ -18,6 +28,11 @@ impl RCLGuardCondition {
 
 impl Drop for RCLGuardCondition {
     fn drop(&mut self) {
+        if self.node.is_some() {
+            // the graph guard condition is finalized by the node
+            return;
+        }
+
         let guard = rcl::MT_UNSAFE_FN.lock();
         guard.rcl_guard_condition_fini(self.cond.as_mut()).unwrap();
     }
@@ -44,11 +59,33 @@ impl GuardCondition {
 
         let cond = Arc::new(RCLGuardCondition {
             cond: Box::new(guard_condition),
+            node: None,
             _context: context,
         });
         Ok(GuardCondition { cond })
     }
 
+    /// Create a guard condition which is triggered when the ROS graph changes.
+    #[allow(clippy::arc_with_non_send_sync)] // GuardCondition implements Send and Sync
+    pub(crate) fn new_graph(node: Arc<Node>) -> RCLResult<Self> {
+        let graph_cond = {
+            let guard = rcl::MT_UNSAFE_FN.lock();
+            guard.rcl_node_get_graph_guard_condition(node.as_ptr())
+        };
+
+        if graph_cond.is_null() {
+            return Err(RCLError::NodeInvalid);
+        }
+
+        // The implementation of the guard condition is shared with the node.
+        let cond = Arc::new(RCLGuardCondition {
+            cond: Box::new(unsafe { *graph_cond }),
+            _context: node.context.clone(),
+            node: Some(node),
+        });
+        Ok(GuardCondition { cond })
+    }
+
     pub(crate) fn trigger(&self) -> RCLResult<()> {
         let guard = rcl::MT_UNSAFE_FN.lock();
         guard.rcl_trigger_guard_condition(unsafe { self.cond.as_ptr_mut() })
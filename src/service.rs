@@ -6,6 +6,9 @@ use std::time::SystemTime;
 pub mod client;
 pub mod server;
 
+#[cfg(not(any(feature = "humble", feature = "galactic")))]
+pub mod introspection;
+
 /// `Header` contains information about timestamps of source and destination, a sequence number, and a guid.
 #[derive(Debug)]
 pub struct Header {
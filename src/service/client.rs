@@ -54,36 +54,85 @@
 //! async_std::task::block_on(run_client(client, logger)); // Spawn an asynchronous task.
 //! ```
 
+#[cfg(not(any(feature = "humble", feature = "galactic")))]
+use super::introspection::{IntrospectionLock, ServiceIntrospection, ServiceIntrospectionState};
 use super::Header;
 use crate::{
+    context::ContextShutdown,
     error::{DynError, RCLError, RCLResult},
     get_allocator, is_halt,
+    logger::{pr_fatal_in, Logger},
     msg::ServiceMsg,
     node::Node,
     qos::Profile,
     rcl,
     selector::{
         async_selector::{self, SELECTOR},
-        CallbackResult, Selector,
+        CallbackResult, Selector, TimerRequests,
     },
     signal_handler::Signaled,
     PhantomUnsync, RecvResult, ST,
 };
+use parking_lot::Mutex;
 use pin_project::{pin_project, pinned_drop};
 use std::{
-    ffi::CString, future::Future, marker::PhantomData, mem::MaybeUninit, os::raw::c_void, pin::Pin,
-    sync::Arc, task::Poll, time::Duration,
+    cell::{RefCell, UnsafeCell},
+    collections::BTreeMap,
+    ffi::CString,
+    future::Future,
+    marker::PhantomData,
+    mem::{self, MaybeUninit},
+    os::raw::c_void,
+    pin::Pin,
+    rc::Rc,
+    sync::Arc,
+    task::{Poll, Waker},
+    time::{Duration, SystemTime},
 };
 
 pub(crate) struct ClientData {
-    pub(crate) client: rcl::rcl_client_t,
+    client: UnsafeCell<rcl::rcl_client_t>,
     pub(crate) node: Arc<Node>,
+
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub(crate) introspection: IntrospectionLock,
 }
 
 impl Drop for ClientData {
     fn drop(&mut self) {
         let guard = rcl::MT_UNSAFE_FN.lock();
-        let _ = guard.rcl_client_fini(&mut self.client, unsafe { self.node.as_ptr_mut() });
+        let _ = guard.rcl_client_fini(self.client.get_mut(), unsafe { self.node.as_ptr_mut() });
+    }
+}
+
+impl ClientData {
+    pub(crate) fn as_ptr(&self) -> *const rcl::rcl_client_t {
+        self.client.get()
+    }
+
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub(crate) fn as_ptr_mut(&self) -> *mut rcl::rcl_client_t {
+        self.client.get()
+    }
+
+    fn is_service_available(&self) -> RCLResult<bool> {
+        let mut is_available = false;
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        guard.rcl_service_server_is_available(
+            self.node.as_ptr(),
+            self.as_ptr(),
+            &mut is_available,
+        )?;
+        Ok(is_available)
+    }
+
+    /// Send a request.
+    /// On iron, this is serialized with configuring service introspection.
+    fn send_request(&self, data: *const c_void, seq: &mut i64) -> RCLResult<()> {
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        let _introspection = self.introspection.lock();
+
+        rcl::MTSafeFn::rcl_send_request(self.as_ptr(), data, seq)
     }
 }
 
@@ -92,7 +141,7 @@ unsafe impl Send for ClientData {}
 
 /// Client.
 pub struct Client<T> {
-    data: Arc<ClientData>,
+    pub(crate) data: Arc<ClientData>,
     _phantom: PhantomData<T>,
     _unsync: PhantomUnsync,
 }
@@ -121,12 +170,124 @@ impl<T: ServiceMsg> Client<T> {
         )?;
 
         Ok(Client {
-            data: Arc::new(ClientData { client, node }),
+            data: Arc::new(ClientData {
+                client: UnsafeCell::new(client),
+                node,
+
+                #[cfg(not(any(feature = "humble", feature = "galactic")))]
+                introspection: Default::default(),
+            }),
             _phantom: Default::default(),
             _unsync: Default::default(),
         })
     }
 
+    /// Check whether a server of the service is available.
+    pub fn is_service_available(&self) -> RCLResult<bool> {
+        self.data.is_service_available()
+    }
+
+    /// Configure service introspection, which is available on iron.
+    /// If `qos` is specified `None`, the default profile is used
+    /// to publish events.
+    ///
+    /// See `service::introspection`.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::NodeInvalid` if the node is invalid, or
+    /// - `RCLError::BadAlloc` if allocating memory failed, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub fn configure_introspection(
+        &self,
+        qos: Option<Profile>,
+        state: ServiceIntrospectionState,
+    ) -> RCLResult<()> {
+        self.get_introspection().configure(qos, state)
+    }
+
+    /// Get a handle to configure service introspection,
+    /// which can be used after the client is moved.
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub fn get_introspection(&self) -> ServiceIntrospection {
+        ServiceIntrospection::new_client(self.data.clone(), <T as ServiceMsg>::type_support())
+    }
+
+    /// Get the QoS profile actually used by the middleware to publish requests.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::ClientInvalid` if the client is invalid.
+    #[cfg(any(feature = "humble", feature = "iron"))]
+    pub fn get_request_publisher_actual_qos(&self) -> RCLResult<Profile> {
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        Profile::from_actual_qos(
+            guard.rcl_client_request_publisher_get_actual_qos(self.data.as_ptr()),
+            RCLError::ClientInvalid,
+        )
+    }
+
+    /// Get the QoS profile actually used by the middleware to subscribe responses.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::ClientInvalid` if the client is invalid.
+    #[cfg(any(feature = "humble", feature = "iron"))]
+    pub fn get_response_subscription_actual_qos(&self) -> RCLResult<Profile> {
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        Profile::from_actual_qos(
+            guard.rcl_client_response_subscription_get_actual_qos(self.data.as_ptr()),
+            RCLError::ClientInvalid,
+        )
+    }
+
+    /// Block until a server of the service becomes available or `timeout` elapses.
+    /// This returns `false` if timed out.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{msg::common_interfaces::std_srvs, service::client::Client};
+    /// use std::time::Duration;
+    ///
+    /// fn wait_server(client: &Client<std_srvs::srv::Empty>) {
+    ///     if !client.wait_for_service(Duration::from_secs(1)).unwrap() {
+    ///         println!("server is not available");
+    ///     }
+    /// }
+    /// ```
+    pub fn wait_for_service(&self, timeout: Duration) -> Result<bool, DynError> {
+        self.data
+            .node
+            .wait_graph(timeout, || Ok(self.data.is_service_available()?))
+    }
+
+    /// Wait until a server of the service becomes available asynchronously.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{msg::common_interfaces::std_srvs, service::client::Client};
+    /// use std::time::Duration;
+    ///
+    /// async fn wait_server(client: &Client<std_srvs::srv::Empty>) {
+    ///     let dur = Duration::from_secs(1);
+    ///     if async_std::future::timeout(dur, client.wait_for_service_async())
+    ///         .await
+    ///         .is_err()
+    ///     {
+    ///         println!("server is not available");
+    ///     }
+    /// }
+    /// ```
+    pub fn wait_for_service_async(&self) -> impl Future<Output = Result<(), DynError>> {
+        let data = self.data.clone();
+        let node = data.node.clone();
+        node.wait_graph_async(move || Ok(data.is_service_available()?))
+    }
+
     /// Send a request.
     ///
     /// # Example
@@ -218,11 +379,8 @@ impl<T: ServiceMsg> Client<T> {
         data: &<T as ServiceMsg>::Request,
     ) -> RCLResult<(ClientRecv<T>, i64)> {
         let mut seq: i64 = 0;
-        rcl::MTSafeFn::rcl_send_request(
-            &self.data.client,
-            data as *const _ as *const c_void,
-            &mut seq,
-        )?;
+        self.data
+            .send_request(data as *const _ as *const c_void, &mut seq)?;
 
         Ok((
             ClientRecv {
@@ -234,6 +392,19 @@ impl<T: ServiceMsg> Client<T> {
             seq,
         ))
     }
+
+    /// Convert the client into a `PipelinedClient`,
+    /// which can send requests without waiting for responses.
+    pub fn into_pipelined(self) -> PipelinedClient<T> {
+        PipelinedClient {
+            pipeline: Arc::new(Pipeline {
+                data: self.data,
+                responses: Mutex::new(BTreeMap::new()),
+                wakers: Default::default(),
+            }),
+            _unsync: Default::default(),
+        }
+    }
 }
 
 /// Receiver to receive a response.
@@ -259,7 +430,7 @@ impl<T: ServiceMsg> ClientRecv<T> {
     /// - `RCLError::Error` if an unspecified error occurs.
     pub fn try_recv(self) -> RecvResult<(Client<T>, <T as ServiceMsg>::Response, Header), Self> {
         let (response, header) = match rcl_take_response_with_info::<<T as ServiceMsg>::Response>(
-            &self.data.client,
+            self.data.as_ptr(),
             self.seq,
         ) {
             Ok(data) => data,
@@ -432,7 +603,7 @@ impl<T: ServiceMsg> ClientRecv<T> {
 }
 
 fn rcl_take_response_with_info<T>(
-    client: &rcl::rcl_client_t,
+    client: *const rcl::rcl_client_t,
     seq: i64,
 ) -> RCLResult<(T, rcl::rmw_service_info_t)> {
     let mut header: rcl::rmw_service_info_t = unsafe { MaybeUninit::zeroed().assume_init() };
@@ -479,11 +650,15 @@ impl<T: ServiceMsg> Future for AsyncReceiver<T> {
             return Poll::Ready(Err(Signaled.into()));
         }
 
+        if !self.client.data.node.context.is_valid() {
+            return Poll::Ready(Err(ContextShutdown.into()));
+        }
+
         let this = self.project();
 
         *this.is_waiting = false;
 
-        match rcl_take_response_with_info(&this.client.data.client, this.client.seq) {
+        match rcl_take_response_with_info(this.client.data.as_ptr(), this.client.seq) {
             Ok((val, header)) => {
                 if header.request_id.sequence_number == this.client.seq {
                     return Poll::Ready(Ok((
@@ -535,3 +710,457 @@ impl<T> PinnedDrop for AsyncReceiver<T> {
         }
     }
 }
+
+/// Client which can have multiple outstanding requests.
+/// Responses are routed to `PendingResponse`s by sequence numbers.
+///
+/// `PipelinedClient` is created by `Client::into_pipelined`.
+/// If a `PendingResponse` is dropped, for example when it timed out,
+/// the response of the request is discarded when it arrives.
+///
+/// # Example
+///
+/// ```
+/// use safe_drive::{
+///     error::DynError, msg::common_interfaces::std_srvs, service::client::PipelinedClient,
+/// };
+/// use std::time::Duration;
+///
+/// async fn run_client(client: PipelinedClient<std_srvs::srv::Empty>) -> Result<(), DynError> {
+///     // Send requests without waiting for responses.
+///     let mut receivers = Vec::new();
+///     for _ in 0..10 {
+///         let request = std_srvs::srv::EmptyRequest::new().unwrap();
+///         receivers.push(client.send(&request)?);
+///     }
+///
+///     // Receive responses.
+///     let dur = Duration::from_millis(100);
+///     for receiver in receivers {
+///         let seq = receiver.get_sequence();
+///         match async_std::future::timeout(dur, receiver).await {
+///             Ok(Ok((_response, header))) => assert_eq!(header.get_sequence(), seq),
+///             Ok(Err(e)) => return Err(e),
+///             Err(_) => println!("timeout: sequence = {seq}"), // The request is forgotten.
+///         }
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub struct PipelinedClient<T: ServiceMsg> {
+    pipeline: Arc<Pipeline<T>>,
+    _unsync: PhantomUnsync,
+}
+
+impl<T: ServiceMsg> PipelinedClient<T> {
+    /// Check whether a server of the service is available.
+    pub fn is_service_available(&self) -> RCLResult<bool> {
+        self.pipeline.data.is_service_available()
+    }
+
+    /// Send a request and return a receiver of the response.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::ClientInvalid` if the client is invalid, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn send(&self, data: &<T as ServiceMsg>::Request) -> RCLResult<PendingResponse<T>> {
+        // Hold the lock so that the response is not discarded before the registration.
+        let mut responses = self.pipeline.responses.lock();
+
+        let mut seq: i64 = 0;
+        self.pipeline
+            .data
+            .send_request(data as *const _ as *const c_void, &mut seq)?;
+
+        responses.insert(seq, None);
+
+        Ok(PendingResponse {
+            pipeline: self.pipeline.clone(),
+            seq,
+        })
+    }
+
+    /// Get the number of requests whose `PendingResponse`s are alive.
+    pub fn num_pending(&self) -> usize {
+        self.pipeline.responses.lock().len()
+    }
+}
+
+type Responses<T> = BTreeMap<i64, Option<(<T as ServiceMsg>::Response, Header)>>;
+
+struct Pipeline<T: ServiceMsg> {
+    data: Arc<ClientData>,
+
+    /// Sequence numbers of outstanding requests and received responses.
+    responses: Mutex<Responses<T>>,
+
+    wakers: Arc<Mutex<PipelineWakers>>,
+}
+
+#[derive(Default)]
+struct PipelineWakers {
+    wakers: BTreeMap<i64, Waker>,
+
+    /// Whether the client is registered to the async selector.
+    is_waiting: bool,
+}
+
+impl<T: ServiceMsg> Pipeline<T> {
+    /// Take all arrived responses, and then return the response of `seq` if it has arrived.
+    /// Receivers waiting for the other taken responses are woken up,
+    /// and responses of forgotten requests are discarded.
+    fn take(&self, seq: i64) -> RCLResult<Option<(<T as ServiceMsg>::Response, Header)>> {
+        let mut responses = self.responses.lock();
+        let mut woken = Vec::new();
+
+        let result = loop {
+            match rcl_take_response_with_info::<<T as ServiceMsg>::Response>(self.data.as_ptr(), 0)
+            {
+                Ok((response, header)) => {
+                    let taken = header.request_id.sequence_number;
+                    if let Some(slot) = responses.get_mut(&taken) {
+                        *slot = Some((response, Header { header }));
+                        if taken != seq {
+                            if let Some(waker) = self.wakers.lock().wakers.remove(&taken) {
+                                woken.push(waker);
+                            }
+                        }
+                    }
+                }
+                Err(RCLError::ClientTakeFailed) => break Ok(()),
+                Err(e) => break Err(e),
+            }
+        };
+
+        let result = result.map(|_| match responses.get_mut(&seq) {
+            Some(slot) if slot.is_some() => {
+                let response = slot.take();
+                responses.remove(&seq);
+                response
+            }
+            _ => None,
+        });
+        drop(responses);
+
+        for waker in woken {
+            waker.wake();
+        }
+
+        result
+    }
+
+    /// Wake `waker` up when responses arrive.
+    fn register_waker(&self, seq: i64, waker: Waker) -> Result<(), DynError> {
+        {
+            let mut guard = self.wakers.lock();
+            guard.wakers.insert(seq, waker);
+            if guard.is_waiting {
+                return Ok(());
+            }
+            guard.is_waiting = true;
+        }
+
+        // Wake all the receivers up, and they take the responses.
+        let wakers = self.wakers.clone();
+        let mut guard = SELECTOR.lock();
+        let result = guard.send_command(
+            &self.data.node.context,
+            async_selector::Command::Client(
+                self.data.clone(),
+                Box::new(move || {
+                    let woken = {
+                        let mut guard = wakers.lock();
+                        guard.is_waiting = false;
+                        mem::take(&mut guard.wakers)
+                    };
+
+                    for waker in woken.into_values() {
+                        waker.wake();
+                    }
+
+                    CallbackResult::Ok
+                }),
+            ),
+        );
+
+        if result.is_err() {
+            self.wakers.lock().is_waiting = false;
+        }
+
+        result
+    }
+
+    /// Forget the request of `seq`.
+    fn forget(&self, seq: i64) {
+        self.responses.lock().remove(&seq);
+
+        let mut guard = self.wakers.lock();
+        guard.wakers.remove(&seq);
+        if guard.is_waiting && guard.wakers.is_empty() {
+            guard.is_waiting = false;
+            drop(guard);
+
+            let mut guard = SELECTOR.lock();
+            let _ = guard.send_command(
+                &self.data.node.context,
+                async_selector::Command::RemoveClient(self.data.clone()),
+            );
+        }
+    }
+}
+
+/// Receiver of a response to a request sent by `PipelinedClient`.
+/// This is a `Future` to receive the response asynchronously.
+///
+/// Dropping the receiver forgets the request,
+/// and the response is discarded when it arrives.
+#[must_use]
+pub struct PendingResponse<T: ServiceMsg> {
+    pipeline: Arc<Pipeline<T>>,
+    seq: i64,
+}
+
+impl<T: ServiceMsg> PendingResponse<T> {
+    /// Get the sequence number of the request.
+    pub fn get_sequence(&self) -> i64 {
+        self.seq
+    }
+
+    /// Receive the response.
+    /// `try_recv` is a non-blocking function, and this
+    /// returns `RecvResult::RetryLater(self)` if the response has not arrived yet.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::ClientInvalid` if the client is invalid, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn try_recv(self) -> RecvResult<(<T as ServiceMsg>::Response, Header), Self> {
+        match self.pipeline.take(self.seq) {
+            Ok(Some(response)) => RecvResult::Ok(response),
+            Ok(None) => RecvResult::RetryLater(self),
+            Err(e) => RecvResult::Err(e.into()),
+        }
+    }
+
+    /// Receive the response with timeout.
+    /// This returns `RecvResult::RetryLater(self)` if timed out,
+    /// and the receiver can be dropped to forget the request.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::ClientInvalid` if the client is invalid, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn recv_timeout(
+        self,
+        t: Duration,
+        selector: &mut Selector,
+    ) -> RecvResult<(<T as ServiceMsg>::Response, Header), Self> {
+        let receiver = match self.try_recv() {
+            RecvResult::RetryLater(receiver) => receiver,
+            result => return result,
+        };
+
+        // Add the client.
+        selector.add_client_data(receiver.pipeline.data.clone(), None, true);
+
+        // Wait a response with timeout.
+        match selector.wait_timeout(t) {
+            Ok(true) => receiver.try_recv(),
+            Ok(false) => RecvResult::RetryLater(receiver), // Timeout.
+            Err(e) => RecvResult::Err(e),
+        }
+    }
+}
+
+impl<T: ServiceMsg> Future for PendingResponse<T> {
+    type Output = Result<(<T as ServiceMsg>::Response, Header), DynError>;
+
+    fn poll(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        if is_halt() {
+            return Poll::Ready(Err(Signaled.into()));
+        }
+
+        if !self.pipeline.data.node.context.is_valid() {
+            return Poll::Ready(Err(ContextShutdown.into()));
+        }
+
+        match self.pipeline.take(self.seq) {
+            Ok(Some(response)) => return Poll::Ready(Ok(response)),
+            Ok(None) => (),
+            Err(e) => return Poll::Ready(Err(e.into())),
+        }
+
+        // wait message arrival
+        if let Err(e) = self.pipeline.register_waker(self.seq, cx.waker().clone()) {
+            return Poll::Ready(Err(e));
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<T: ServiceMsg> Drop for PendingResponse<T> {
+    fn drop(&mut self) {
+        self.pipeline.forget(self.seq);
+    }
+}
+
+/// Callback function invoked when a response arrives.
+/// `None` is passed if the request timed out or receiving the response failed.
+pub type ClientCallback<T> = Box<dyn FnOnce(Option<(<T as ServiceMsg>::Response, Header)>)>;
+
+type Callbacks<T> = Rc<RefCell<BTreeMap<i64, (PendingResponse<T>, ClientCallback<T>)>>>;
+
+/// Client whose responses are handled by callback functions in a `Selector`.
+///
+/// `CallbackClient` is created by `Selector::add_client`,
+/// and requests can be sent in callback functions of the selector.
+///
+/// # Example
+///
+/// ```
+/// use safe_drive::{
+///     logger::Logger, msg::common_interfaces::std_srvs, pr_info, pr_warn, selector::Selector,
+///     service::client::Client,
+/// };
+/// use std::time::Duration;
+///
+/// fn add_client(selector: &mut Selector, client: Client<std_srvs::srv::Empty>) {
+///     let client = selector.add_client(client).unwrap();
+///
+///     // Send a request periodically.
+///     selector.add_wall_timer(
+///         "callback_client_rs",
+///         Duration::from_millis(100),
+///         Box::new(move || {
+///             let request = std_srvs::srv::EmptyRequest::new().unwrap();
+///             client
+///                 .call_with_callback(
+///                     &request,
+///                     Some(Duration::from_millis(50)),
+///                     Box::new(|response| {
+///                         let logger = Logger::new("callback_client_rs");
+///                         match response {
+///                             Some((_response, header)) => pr_info!(logger, "received: {header:?}"),
+///                             None => pr_warn!(logger, "timeout"),
+///                         }
+///                     }),
+///                 )
+///                 .unwrap();
+///         }),
+///     );
+/// }
+/// ```
+pub struct CallbackClient<T: ServiceMsg> {
+    client: PipelinedClient<T>,
+    callbacks: Callbacks<T>,
+    timer_requests: TimerRequests,
+}
+
+impl<T: ServiceMsg + 'static> CallbackClient<T> {
+    pub(crate) fn new(client: Client<T>, timer_requests: TimerRequests) -> Self {
+        CallbackClient {
+            client: client.into_pipelined(),
+            callbacks: Default::default(),
+            timer_requests,
+        }
+    }
+
+    pub(crate) fn data(&self) -> Arc<ClientData> {
+        self.client.pipeline.data.clone()
+    }
+
+    /// Return a function to be invoked by the selector when responses arrive.
+    pub(crate) fn handler(&self) -> Box<dyn FnMut() -> CallbackResult> {
+        let callbacks = self.callbacks.clone();
+
+        Box::new(move || {
+            let seqs: Vec<i64> = callbacks.borrow().keys().copied().collect();
+
+            for seq in seqs {
+                let Some((receiver, callback)) = callbacks.borrow_mut().remove(&seq) else {
+                    continue;
+                };
+
+                match receiver.try_recv() {
+                    RecvResult::Ok(response) => callback(Some(response)),
+                    RecvResult::RetryLater(receiver) => {
+                        callbacks.borrow_mut().insert(seq, (receiver, callback));
+                    }
+                    RecvResult::Err(e) => {
+                        let logger = Logger::new("safe_drive");
+                        pr_fatal_in!(logger, "failed try_recv() of client: {}", e);
+
+                        // No response arrives after the client is removed.
+                        callback(None);
+                        let pending = mem::take(&mut *callbacks.borrow_mut());
+                        for (_receiver, callback) in pending.into_values() {
+                            callback(None);
+                        }
+
+                        return CallbackResult::Remove;
+                    }
+                }
+            }
+
+            CallbackResult::Ok
+        })
+    }
+
+    /// Check whether a server of the service is available.
+    pub fn is_service_available(&self) -> RCLResult<bool> {
+        self.client.is_service_available()
+    }
+
+    /// Send a request, and `handler` is invoked by the selector when the response arrives.
+    /// If `timeout` is specified and the response does not arrive in time,
+    /// `handler` is invoked with `None` and the response is discarded.
+    /// `handler` is also invoked with `None` if receiving responses failed.
+    ///
+    /// This returns the sequence number of the request.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::ClientInvalid` if the client is invalid, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn call_with_callback(
+        &self,
+        data: &<T as ServiceMsg>::Request,
+        timeout: Option<Duration>,
+        handler: ClientCallback<T>,
+    ) -> RCLResult<i64> {
+        let receiver = self.client.send(data)?;
+        let seq = receiver.get_sequence();
+        self.callbacks.borrow_mut().insert(seq, (receiver, handler));
+
+        if let Some(timeout) = timeout {
+            let callbacks = self.callbacks.clone();
+            self.timer_requests.borrow_mut().push((
+                SystemTime::now() + timeout,
+                Box::new(move || {
+                    // Dropping the receiver forgets the request.
+                    let entry = callbacks.borrow_mut().remove(&seq);
+                    if let Some((_receiver, callback)) = entry {
+                        callback(None);
+                    }
+                }),
+            ));
+        }
+
+        Ok(seq)
+    }
+
+    /// Get the number of requests waiting for responses.
+    pub fn num_pending(&self) -> usize {
+        self.callbacks.borrow().len()
+    }
+}
@@ -0,0 +1,227 @@
+//! Service introspection, which is available on iron.
+//!
+//! Clients and servers with introspection enabled publish events of requests and responses
+//! to `<service name>/_service_event`, and the events can be recorded like topics.
+//! `ServiceIntrospectionState::Metadata` publishes only metadata such as sequence numbers and timestamps,
+//! and `ServiceIntrospectionState::Contents` publishes requests and responses together.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::{
+//!     context::Context, msg::common_interfaces::std_srvs,
+//!     service::introspection::ServiceIntrospectionState,
+//! };
+//!
+//! let ctx = Context::new().unwrap();
+//! let node = ctx
+//!     .create_node("introspection_rs", None, Default::default())
+//!     .unwrap();
+//!
+//! let server = node
+//!     .create_server::<std_srvs::srv::Empty>("introspection_rs_service", None)
+//!     .unwrap();
+//!
+//! // Publish requests and responses to `/introspection_rs_service/_service_event`.
+//! server
+//!     .configure_introspection(None, ServiceIntrospectionState::Contents)
+//!     .unwrap();
+//!
+//! // `ServiceIntrospection` can be used after the server is moved.
+//! let introspection = server.get_introspection();
+//! let mut selector = ctx.create_selector().unwrap();
+//! selector.add_server(
+//!     server,
+//!     Box::new(|_request, _header| std_srvs::srv::EmptyResponse::new().unwrap()),
+//! );
+//!
+//! // Disable introspection by a parameter value.
+//! let value = safe_drive::parameter::Value::String("off".to_string());
+//! introspection.configure_by_parameter(None, &value).unwrap();
+//! ```
+
+use super::{client::ClientData, server::ServerData};
+use crate::{
+    clock::Clock,
+    error::{DynError, RCLResult},
+    get_allocator,
+    parameter::Value,
+    qos::Profile,
+    rcl,
+};
+use parking_lot::Mutex;
+use std::{fmt::Display, str::FromStr, sync::Arc};
+
+/// State of service introspection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceIntrospectionState {
+    /// Introspection disabled.
+    Off,
+
+    /// Publish only metadata of requests and responses.
+    Metadata,
+
+    /// Publish metadata and contents of requests and responses.
+    Contents,
+}
+
+impl ServiceIntrospectionState {
+    fn as_rcl(&self) -> rcl::rcl_service_introspection_state_t {
+        match self {
+            Self::Off => rcl::rcl_service_introspection_state_e_RCL_SERVICE_INTROSPECTION_OFF,
+            Self::Metadata => {
+                rcl::rcl_service_introspection_state_e_RCL_SERVICE_INTROSPECTION_METADATA
+            }
+            Self::Contents => {
+                rcl::rcl_service_introspection_state_e_RCL_SERVICE_INTROSPECTION_CONTENTS
+            }
+        }
+    }
+}
+
+impl FromStr for ServiceIntrospectionState {
+    type Err = DynError;
+
+    /// Parse `"off"`, `"metadata"`, or `"contents"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(Self::Off),
+            "metadata" => Ok(Self::Metadata),
+            "contents" => Ok(Self::Contents),
+            _ => Err(format!("invalid service introspection state: {s}").into()),
+        }
+    }
+}
+
+impl Display for ServiceIntrospectionState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Off => "off",
+            Self::Metadata => "metadata",
+            Self::Contents => "contents",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// Lock to serialize configuring service introspection and sending requests or responses,
+/// because configuring replaces the publisher of events used by sending.
+/// This also holds the clock to stamp events, which must outlive the client or server.
+pub(crate) type IntrospectionLock = Mutex<Option<Clock>>;
+
+#[derive(Clone)]
+enum Target {
+    Client(Arc<ClientData>),
+    Server(Arc<ServerData>),
+}
+
+/// Handle to configure service introspection of a client or server.
+/// This is created by `Client::get_introspection` or `Server::get_introspection`,
+/// and can be used even after the client or server is moved,
+/// for example, in callbacks of a parameter server.
+#[derive(Clone)]
+pub struct ServiceIntrospection {
+    target: Target,
+    type_support: *const rcl::rosidl_service_type_support_t,
+}
+
+impl ServiceIntrospection {
+    pub(crate) fn new_client(
+        data: Arc<ClientData>,
+        type_support: *const rcl::rosidl_service_type_support_t,
+    ) -> Self {
+        ServiceIntrospection {
+            target: Target::Client(data),
+            type_support,
+        }
+    }
+
+    pub(crate) fn new_server(
+        data: Arc<ServerData>,
+        type_support: *const rcl::rosidl_service_type_support_t,
+    ) -> Self {
+        ServiceIntrospection {
+            target: Target::Server(data),
+            type_support,
+        }
+    }
+
+    /// Configure service introspection.
+    /// If `qos` is specified `None`, the default profile is used
+    /// to publish events.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::NodeInvalid` if the node is invalid, or
+    /// - `RCLError::BadAlloc` if allocating memory failed, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn configure(
+        &self,
+        qos: Option<Profile>,
+        state: ServiceIntrospectionState,
+    ) -> RCLResult<()> {
+        let lock = match &self.target {
+            Target::Client(data) => &data.introspection,
+            Target::Server(data) => &data.introspection,
+        };
+
+        // Hold the lock until configured so that no request or response is sent meanwhile.
+        let mut introspection = lock.lock();
+        if introspection.is_none() {
+            *introspection = Some(Clock::new()?);
+        }
+        let clock = introspection.as_ref().unwrap().as_ptr_mut();
+
+        let qos = qos.unwrap_or_default();
+        let options = rcl::rcl_publisher_options_t {
+            qos: (&qos).into(),
+            allocator: get_allocator(),
+            rmw_publisher_options: rcl::MTSafeFn::rmw_get_default_publisher_options(),
+            disable_loaned_message: true,
+        };
+
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        match &self.target {
+            Target::Client(data) => guard.rcl_client_configure_service_introspection(
+                data.as_ptr_mut(),
+                unsafe { data.node.as_ptr_mut() },
+                clock,
+                self.type_support,
+                options,
+                state.as_rcl(),
+            ),
+            Target::Server(data) => guard.rcl_service_configure_service_introspection(
+                data.as_ptr_mut(),
+                unsafe { data.node.as_ptr_mut() },
+                clock,
+                self.type_support,
+                options,
+                state.as_rcl(),
+            ),
+        }
+    }
+
+    /// Configure service introspection by a parameter value,
+    /// which must be `"off"`, `"metadata"`, or `"contents"`.
+    ///
+    /// # Errors
+    ///
+    /// - an error if `value` is invalid, or
+    /// - errors of `configure`.
+    pub fn configure_by_parameter(
+        &self,
+        qos: Option<Profile>,
+        value: &Value,
+    ) -> Result<(), DynError> {
+        let state = match value {
+            Value::String(s) => s.parse()?,
+            _ => return Err(format!("invalid service introspection state: {value}").into()),
+        };
+
+        Ok(self.configure(qos, state)?)
+    }
+}
+
+unsafe impl Sync for ServiceIntrospection {}
+unsafe impl Send for ServiceIntrospection {}
@@ -88,8 +88,11 @@
 //! // async_std::task::block_on(server_task(server, logger)); // Spawn an asynchronous task.
 //! ```
 
+#[cfg(not(any(feature = "humble", feature = "galactic")))]
+use super::introspection::{IntrospectionLock, ServiceIntrospection, ServiceIntrospectionState};
 use super::Header;
 use crate::{
+    context::ContextShutdown,
     error::{DynError, RCLError, RCLResult},
     get_allocator, is_halt,
     msg::ServiceMsg,
@@ -105,19 +108,42 @@ use crate::{
 };
 use pin_project::{pin_project, pinned_drop};
 use std::{
-    ffi::CString, future::Future, marker::PhantomData, mem::MaybeUninit, os::raw::c_void, pin::Pin,
-    sync::Arc, task::Poll,
+    cell::UnsafeCell, ffi::CString, future::Future, marker::PhantomData, mem::MaybeUninit,
+    os::raw::c_void, pin::Pin, sync::Arc, task::Poll,
 };
 
 pub(crate) struct ServerData {
-    pub(crate) service: rcl::rcl_service_t,
+    service: UnsafeCell<rcl::rcl_service_t>,
     pub(crate) node: Arc<Node>,
+
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub(crate) introspection: IntrospectionLock,
 }
 
 impl Drop for ServerData {
     fn drop(&mut self) {
         let guard = rcl::MT_UNSAFE_FN.lock();
-        let _ = guard.rcl_service_fini(&mut self.service, unsafe { self.node.as_ptr_mut() });
+        let _ = guard.rcl_service_fini(self.service.get_mut(), unsafe { self.node.as_ptr_mut() });
+    }
+}
+
+impl ServerData {
+    pub(crate) fn as_ptr(&self) -> *const rcl::rcl_service_t {
+        self.service.get()
+    }
+
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub(crate) fn as_ptr_mut(&self) -> *mut rcl::rcl_service_t {
+        self.service.get()
+    }
+
+    /// Send a response.
+    /// On iron, this is serialized with configuring service introspection.
+    fn send_response(&self, request_id: &mut rmw_request_id_t, data: *mut c_void) -> RCLResult<()> {
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        let _introspection = self.introspection.lock();
+
+        rcl::MTSafeFn::rcl_send_response(self.as_ptr(), request_id, data)
     }
 }
 
@@ -158,12 +184,74 @@ impl<T: ServiceMsg> Server<T> {
         }
 
         Ok(Server {
-            data: Arc::new(ServerData { service, node }),
+            data: Arc::new(ServerData {
+                service: UnsafeCell::new(service),
+                node,
+
+                #[cfg(not(any(feature = "humble", feature = "galactic")))]
+                introspection: Default::default(),
+            }),
             _phantom: Default::default(),
             _unsync: Default::default(),
         })
     }
 
+    /// Configure service introspection, which is available on iron.
+    /// If `qos` is specified `None`, the default profile is used
+    /// to publish events.
+    ///
+    /// See `service::introspection`.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::NodeInvalid` if the node is invalid, or
+    /// - `RCLError::BadAlloc` if allocating memory failed, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub fn configure_introspection(
+        &self,
+        qos: Option<Profile>,
+        state: ServiceIntrospectionState,
+    ) -> RCLResult<()> {
+        self.get_introspection().configure(qos, state)
+    }
+
+    /// Get a handle to configure service introspection,
+    /// which can be used after the server is moved.
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub fn get_introspection(&self) -> ServiceIntrospection {
+        ServiceIntrospection::new_server(self.data.clone(), <T as ServiceMsg>::type_support())
+    }
+
+    /// Get the QoS profile actually used by the middleware to subscribe requests.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::ServiceInvalid` if the server is invalid.
+    #[cfg(any(feature = "humble", feature = "iron"))]
+    pub fn get_request_subscription_actual_qos(&self) -> RCLResult<Profile> {
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        Profile::from_actual_qos(
+            guard.rcl_service_request_subscription_get_actual_qos(self.data.as_ptr()),
+            RCLError::ServiceInvalid,
+        )
+    }
+
+    /// Get the QoS profile actually used by the middleware to publish responses.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::ServiceInvalid` if the server is invalid.
+    #[cfg(any(feature = "humble", feature = "iron"))]
+    pub fn get_response_publisher_actual_qos(&self) -> RCLResult<Profile> {
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        Profile::from_actual_qos(
+            guard.rcl_service_response_publisher_get_actual_qos(self.data.as_ptr()),
+            RCLError::ServiceInvalid,
+        )
+    }
+
     /// Receive a request.
     /// `try_recv` is a non-blocking function, and
     /// this returns `RecvResult::RetryLater(self)` if there is no available data.
@@ -216,7 +304,7 @@ impl<T: ServiceMsg> Server<T> {
     #[must_use]
     pub fn try_recv(self) -> RecvResult<(ServerSend<T>, <T as ServiceMsg>::Request, Header), Self> {
         let (request, header) =
-            match rcl_take_request_with_info::<<T as ServiceMsg>::Request>(&self.data.service) {
+            match rcl_take_request_with_info::<<T as ServiceMsg>::Request>(self.data.as_ptr()) {
                 Ok(data) => data,
                 Err(RCLError::ServiceTakeFailed) => return RecvResult::RetryLater(self),
                 Err(e) => return RecvResult::Err(e.into()),
@@ -286,10 +374,153 @@ impl<T: ServiceMsg> Server<T> {
         }
         .await
     }
+
+    /// Convert the server into a `ConcurrentServer`,
+    /// which can receive requests before responding to previous ones.
+    pub fn into_concurrent(self) -> ConcurrentServer<T> {
+        ConcurrentServer {
+            data: self.data,
+            _phantom: Default::default(),
+            _unsync: Default::default(),
+        }
+    }
 }
 
 unsafe impl<T> Send for Server<T> {}
 
+/// Server which keeps receiving requests while responding to previous ones.
+/// Each request has its own `Responder`, which can be sent to another task or thread
+/// and can respond in any order.
+///
+/// `ConcurrentServer` is created by `Server::into_concurrent`.
+///
+/// # Example
+///
+/// ```
+/// use safe_drive::{
+///     logger::Logger, msg::common_interfaces::std_srvs, pr_error, pr_warn,
+///     service::server::ConcurrentServer,
+/// };
+///
+/// async fn server_task(mut server: ConcurrentServer<std_srvs::srv::Empty>, logger: Logger) {
+///     loop {
+///         // Receive a request.
+///         match server.recv().await {
+///             Ok((responder, _request, _header)) => {
+///                 let logger = Logger::new("concurrent_server_rs");
+///
+///                 // Respond in another task without blocking other requests.
+///                 async_std::task::spawn(async move {
+///                     let response = std_srvs::srv::EmptyResponse::new().unwrap();
+///                     if let Err((_, e)) = responder.send(&response) {
+///                         pr_warn!(logger, "failed to respond: {e}");
+///                     }
+///                 });
+///             }
+///             Err(e) => {
+///                 pr_error!(logger, "error: {e}");
+///                 return;
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[must_use]
+pub struct ConcurrentServer<T> {
+    data: Arc<ServerData>,
+    _phantom: PhantomData<T>,
+    _unsync: PhantomUnsync,
+}
+
+impl<T: ServiceMsg> ConcurrentServer<T> {
+    /// Receive a request.
+    /// `try_recv` is a non-blocking function, and
+    /// this returns `RecvResult::RetryLater(())` if there is no available data.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::ServiceInvalid` if the service is invalid, or
+    /// - `RCLError::BadAlloc` if allocating memory failed, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn try_recv(
+        &mut self,
+    ) -> RecvResult<(Responder<T>, <T as ServiceMsg>::Request, Header), ()> {
+        match self.server().try_recv() {
+            RecvResult::Ok((sender, request, header)) => {
+                RecvResult::Ok((sender.into_responder(), request, header))
+            }
+            RecvResult::RetryLater(_) => RecvResult::RetryLater(()),
+            RecvResult::Err(e) => RecvResult::Err(e),
+        }
+    }
+
+    /// Receive a request asynchronously.
+    /// Requests can be received even if `Responder`s of previous requests are alive.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::ServiceInvalid` if the service is invalid, or
+    /// - `RCLError::BadAlloc` if allocating memory failed, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub async fn recv(
+        &mut self,
+    ) -> Result<(Responder<T>, <T as ServiceMsg>::Request, Header), DynError> {
+        let (sender, request, header) = self.server().recv().await?;
+        Ok((sender.into_responder(), request, header))
+    }
+
+    fn server(&self) -> Server<T> {
+        Server {
+            data: self.data.clone(),
+            _phantom: Default::default(),
+            _unsync: Default::default(),
+        }
+    }
+}
+
+unsafe impl<T> Send for ConcurrentServer<T> {}
+
+/// Responder to send a response to a request received by `ConcurrentServer`.
+/// `Responder` can be sent to other threads.
+///
+/// If a `Responder` is dropped without sending a response,
+/// the client never receives the response.
+#[must_use]
+pub struct Responder<T> {
+    data: Arc<ServerData>,
+    request_id: rmw_request_id_t,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: ServiceMsg> Responder<T> {
+    /// Get the sequence number of the request.
+    pub fn get_sequence(&self) -> i64 {
+        self.request_id.sequence_number
+    }
+
+    /// Send a response to the client.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::ServiceInvalid` if the service is invalid, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn send(mut self, data: &<T as ServiceMsg>::Response) -> Result<(), (Self, RCLError)> {
+        if let Err(e) = self
+            .data
+            .send_response(&mut self.request_id, data as *const _ as *mut c_void)
+        {
+            return Err((self, e));
+        }
+
+        Ok(())
+    }
+}
+
+unsafe impl<T> Send for Responder<T> {}
+
 /// Sender to send a response.
 #[must_use]
 pub struct ServerSend<T> {
@@ -345,11 +576,10 @@ impl<T: ServiceMsg> ServerSend<T> {
         mut self,
         data: &<T as ServiceMsg>::Response,
     ) -> Result<Server<T>, (Self, RCLError)> {
-        if let Err(e) = rcl::MTSafeFn::rcl_send_response(
-            &self.data.service,
-            &mut self.request_id,
-            data as *const _ as *mut c_void,
-        ) {
+        if let Err(e) = self
+            .data
+            .send_response(&mut self.request_id, data as *const _ as *mut c_void)
+        {
             return Err((self, e));
         }
 
@@ -367,10 +597,18 @@ impl<T: ServiceMsg> ServerSend<T> {
             _unsync: Default::default(),
         }
     }
+
+    fn into_responder(self) -> Responder<T> {
+        Responder {
+            data: self.data,
+            request_id: self.request_id,
+            _phantom: Default::default(),
+        }
+    }
 }
 
 fn rcl_take_request_with_info<T>(
-    service: &rcl::rcl_service_t,
+    service: *const rcl::rcl_service_t,
 ) -> RCLResult<(T, rcl::rmw_service_info_t)> {
     let mut header: rcl::rmw_service_info_t = unsafe { MaybeUninit::zeroed().assume_init() };
     let mut ros_request: T = unsafe { MaybeUninit::zeroed().assume_init() };
@@ -404,6 +642,10 @@ impl<T: ServiceMsg> Future for AsyncReceiver<T> {
             return Poll::Ready(Err(Signaled.into()));
         }
 
+        if !self.server.data.node.context.is_valid() {
+            return Poll::Ready(Err(ContextShutdown.into()));
+        }
+
         let this = self.project();
 
         // let (server, is_waiting) = unsafe {
@@ -412,7 +654,7 @@ impl<T: ServiceMsg> Future for AsyncReceiver<T> {
         // };
         *this.is_waiting = false;
 
-        match rcl_take_request_with_info::<<T as ServiceMsg>::Request>(&this.server.data.service) {
+        match rcl_take_request_with_info::<<T as ServiceMsg>::Request>(this.server.data.as_ptr()) {
             Ok((request, header)) => Poll::Ready(Ok((
                 ServerSend {
                     data: this.server.data.clone(),
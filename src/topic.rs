@@ -1,4 +1,8 @@
 //! Publish and subscribe messages.
 
+pub mod event;
+pub mod generic;
+pub mod intra_process;
 pub mod publisher;
+pub mod serialized;
 pub mod subscriber;
@@ -0,0 +1,558 @@
+//! QoS events of publishers and subscribers.
+//!
+//! QoS events notify that QoS policies, such as deadline and liveliness, are violated,
+//! or that QoS profiles of a publisher and a subscriber are incompatible.
+//! An event is created by `Publisher::create_event` or `Subscriber::create_event`,
+//! and it can be handled by `Selector::add_event_handler` or `Event::recv`.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::{
+//!     context::Context,
+//!     msg::common_interfaces::std_msgs,
+//!     qos::Profile,
+//!     topic::event::{EventStatus, SubscriberEventType},
+//! };
+//! use std::time::Duration;
+//!
+//! let ctx = Context::new().unwrap();
+//! let node = ctx
+//!     .create_node("event_rs", None, Default::default())
+//!     .unwrap();
+//!
+//! // Messages must arrive every 100ms.
+//! let mut profile = Profile::default();
+//! profile.deadline = Duration::from_millis(100);
+//!
+//! let subscriber = node
+//!     .create_subscriber::<std_msgs::msg::Empty>("event_rs_topic", Some(profile),
+//!     #[cfg(not(any(feature = "humble", feature = "galactic")))]
+//!     true
+//! ).unwrap();
+//!
+//! // Create an event of the subscriber.
+//! let event = subscriber
+//!     .create_event(SubscriberEventType::RequestedDeadlineMissed)
+//!     .unwrap();
+//!
+//! // Register the event with a callback function.
+//! let mut selector = ctx.create_selector().unwrap();
+//! selector.add_event_handler(
+//!     event,
+//!     Box::new(|status| {
+//!         if let EventStatus::RequestedDeadlineMissed(status) = status {
+//!             println!("deadline missed: total = {}", status.total_count);
+//!         }
+//!     }),
+//! );
+//! ```
+
+use super::{publisher::RCLPublisher, subscriber::RCLSubscription};
+use crate::{
+    context::ContextShutdown,
+    error::{DynError, RCLError, RCLResult},
+    is_halt,
+    node::Node,
+    qos::policy::PolicyKind,
+    rcl,
+    selector::{
+        async_selector::{Command, SELECTOR},
+        CallbackResult,
+    },
+    signal_handler::Signaled,
+    PhantomUnsync,
+};
+use num_traits::FromPrimitive;
+use parking_lot::Mutex;
+use std::{
+    future::Future,
+    mem::MaybeUninit,
+    os::raw::c_void,
+    pin::Pin,
+    sync::Arc,
+    task::{self, Poll, Waker},
+};
+
+#[cfg(feature = "galactic")]
+use rcl::{
+    rcl_publisher_event_type_t_RCL_PUBLISHER_LIVELINESS_LOST as RCL_PUBLISHER_LIVELINESS_LOST,
+    rcl_publisher_event_type_t_RCL_PUBLISHER_OFFERED_DEADLINE_MISSED as RCL_PUBLISHER_OFFERED_DEADLINE_MISSED,
+    rcl_publisher_event_type_t_RCL_PUBLISHER_OFFERED_INCOMPATIBLE_QOS as RCL_PUBLISHER_OFFERED_INCOMPATIBLE_QOS,
+    rcl_subscription_event_type_t_RCL_SUBSCRIPTION_LIVELINESS_CHANGED as RCL_SUBSCRIPTION_LIVELINESS_CHANGED,
+    rcl_subscription_event_type_t_RCL_SUBSCRIPTION_MESSAGE_LOST as RCL_SUBSCRIPTION_MESSAGE_LOST,
+    rcl_subscription_event_type_t_RCL_SUBSCRIPTION_REQUESTED_DEADLINE_MISSED as RCL_SUBSCRIPTION_REQUESTED_DEADLINE_MISSED,
+    rcl_subscription_event_type_t_RCL_SUBSCRIPTION_REQUESTED_INCOMPATIBLE_QOS as RCL_SUBSCRIPTION_REQUESTED_INCOMPATIBLE_QOS,
+};
+
+#[cfg(any(feature = "humble", feature = "iron"))]
+use rcl::{
+    rcl_publisher_event_type_e_RCL_PUBLISHER_LIVELINESS_LOST as RCL_PUBLISHER_LIVELINESS_LOST,
+    rcl_publisher_event_type_e_RCL_PUBLISHER_OFFERED_DEADLINE_MISSED as RCL_PUBLISHER_OFFERED_DEADLINE_MISSED,
+    rcl_publisher_event_type_e_RCL_PUBLISHER_OFFERED_INCOMPATIBLE_QOS as RCL_PUBLISHER_OFFERED_INCOMPATIBLE_QOS,
+    rcl_subscription_event_type_e_RCL_SUBSCRIPTION_LIVELINESS_CHANGED as RCL_SUBSCRIPTION_LIVELINESS_CHANGED,
+    rcl_subscription_event_type_e_RCL_SUBSCRIPTION_MESSAGE_LOST as RCL_SUBSCRIPTION_MESSAGE_LOST,
+    rcl_subscription_event_type_e_RCL_SUBSCRIPTION_REQUESTED_DEADLINE_MISSED as RCL_SUBSCRIPTION_REQUESTED_DEADLINE_MISSED,
+    rcl_subscription_event_type_e_RCL_SUBSCRIPTION_REQUESTED_INCOMPATIBLE_QOS as RCL_SUBSCRIPTION_REQUESTED_INCOMPATIBLE_QOS,
+};
+
+/// Types of events of publishers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublisherEventType {
+    /// The publisher did not publish a message within the deadline.
+    OfferedDeadlineMissed,
+
+    /// The publisher did not assert its liveliness within the lease duration.
+    LivelinessLost,
+
+    /// A subscriber requests a QoS profile incompatible with the publisher.
+    OfferedIncompatibleQoS,
+}
+
+impl PublisherEventType {
+    fn to_rcl(self) -> rcl::rcl_publisher_event_type_t {
+        match self {
+            PublisherEventType::OfferedDeadlineMissed => RCL_PUBLISHER_OFFERED_DEADLINE_MISSED,
+            PublisherEventType::LivelinessLost => RCL_PUBLISHER_LIVELINESS_LOST,
+            PublisherEventType::OfferedIncompatibleQoS => RCL_PUBLISHER_OFFERED_INCOMPATIBLE_QOS,
+        }
+    }
+}
+
+/// Types of events of subscribers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubscriberEventType {
+    /// The subscriber did not receive a message within the deadline.
+    RequestedDeadlineMissed,
+
+    /// Some publishers became alive or not alive.
+    LivelinessChanged,
+
+    /// A publisher offers a QoS profile incompatible with the subscriber.
+    RequestedIncompatibleQoS,
+
+    /// Some messages were lost.
+    MessageLost,
+}
+
+impl SubscriberEventType {
+    fn to_rcl(self) -> rcl::rcl_subscription_event_type_t {
+        match self {
+            SubscriberEventType::RequestedDeadlineMissed => {
+                RCL_SUBSCRIPTION_REQUESTED_DEADLINE_MISSED
+            }
+            SubscriberEventType::LivelinessChanged => RCL_SUBSCRIPTION_LIVELINESS_CHANGED,
+            SubscriberEventType::RequestedIncompatibleQoS => {
+                RCL_SUBSCRIPTION_REQUESTED_INCOMPATIBLE_QOS
+            }
+            SubscriberEventType::MessageLost => RCL_SUBSCRIPTION_MESSAGE_LOST,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EventType {
+    Publisher(PublisherEventType),
+    Subscriber(SubscriberEventType),
+}
+
+/// Status of deadline missed events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadlineMissedStatus {
+    /// The total number of missed deadlines.
+    pub total_count: i32,
+
+    /// The number of missed deadlines since the last time the status was taken.
+    pub total_count_change: i32,
+}
+
+impl From<&rcl::rmw_offered_deadline_missed_status_t> for DeadlineMissedStatus {
+    fn from(status: &rcl::rmw_offered_deadline_missed_status_t) -> Self {
+        DeadlineMissedStatus {
+            total_count: status.total_count,
+            total_count_change: status.total_count_change,
+        }
+    }
+}
+
+impl From<&rcl::rmw_requested_deadline_missed_status_t> for DeadlineMissedStatus {
+    fn from(status: &rcl::rmw_requested_deadline_missed_status_t) -> Self {
+        DeadlineMissedStatus {
+            total_count: status.total_count,
+            total_count_change: status.total_count_change,
+        }
+    }
+}
+
+/// Status of liveliness lost events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LivelinessLostStatus {
+    /// The total number of times the liveliness was lost.
+    pub total_count: i32,
+
+    /// The number of times the liveliness was lost since the last time the status was taken.
+    pub total_count_change: i32,
+}
+
+impl From<&rcl::rmw_liveliness_lost_status_t> for LivelinessLostStatus {
+    fn from(status: &rcl::rmw_liveliness_lost_status_t) -> Self {
+        LivelinessLostStatus {
+            total_count: status.total_count,
+            total_count_change: status.total_count_change,
+        }
+    }
+}
+
+/// Status of liveliness changed events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LivelinessChangedStatus {
+    /// The number of alive publishers.
+    pub alive_count: i32,
+
+    /// The number of publishers which are not alive.
+    pub not_alive_count: i32,
+
+    /// The change of `alive_count` since the last time the status was taken.
+    pub alive_count_change: i32,
+
+    /// The change of `not_alive_count` since the last time the status was taken.
+    pub not_alive_count_change: i32,
+}
+
+impl From<&rcl::rmw_liveliness_changed_status_t> for LivelinessChangedStatus {
+    fn from(status: &rcl::rmw_liveliness_changed_status_t) -> Self {
+        LivelinessChangedStatus {
+            alive_count: status.alive_count,
+            not_alive_count: status.not_alive_count,
+            alive_count_change: status.alive_count_change,
+            not_alive_count_change: status.not_alive_count_change,
+        }
+    }
+}
+
+/// Status of incompatible QoS events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncompatibleQoSStatus {
+    /// The total number of incompatible publishers or subscribers found.
+    pub total_count: i32,
+
+    /// The number of incompatible publishers or subscribers found since the last time the status was taken.
+    pub total_count_change: i32,
+
+    /// The kind of the policy found incompatible last.
+    pub last_policy_kind: PolicyKind,
+}
+
+impl From<&rcl::rmw_qos_incompatible_event_status_t> for IncompatibleQoSStatus {
+    fn from(status: &rcl::rmw_qos_incompatible_event_status_t) -> Self {
+        IncompatibleQoSStatus {
+            total_count: status.total_count,
+            total_count_change: status.total_count_change,
+            last_policy_kind: FromPrimitive::from_u32(status.last_policy_kind)
+                .unwrap_or(PolicyKind::Invalid),
+        }
+    }
+}
+
+/// Status of message lost events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageLostStatus {
+    /// The total number of lost messages.
+    pub total_count: usize,
+
+    /// The number of lost messages since the last time the status was taken.
+    pub total_count_change: usize,
+}
+
+impl From<&rcl::rmw_message_lost_status_t> for MessageLostStatus {
+    #[allow(clippy::unnecessary_cast)] // size_t is not usize on galactic
+    fn from(status: &rcl::rmw_message_lost_status_t) -> Self {
+        MessageLostStatus {
+            total_count: status.total_count as usize,
+            total_count_change: status.total_count_change as usize,
+        }
+    }
+}
+
+/// Status of an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventStatus {
+    OfferedDeadlineMissed(DeadlineMissedStatus),
+    LivelinessLost(LivelinessLostStatus),
+    OfferedIncompatibleQoS(IncompatibleQoSStatus),
+    RequestedDeadlineMissed(DeadlineMissedStatus),
+    LivelinessChanged(LivelinessChangedStatus),
+    RequestedIncompatibleQoS(IncompatibleQoSStatus),
+    MessageLost(MessageLostStatus),
+}
+
+pub(crate) struct RCLEvent {
+    pub event: Box<rcl::rcl_event_t>,
+    event_type: EventType,
+    pub node: Arc<Node>,
+
+    // The publisher or the subscription must outlive the event.
+    _publisher: Option<Arc<RCLPublisher>>,
+    _subscription: Option<Arc<RCLSubscription>>,
+}
+
+impl RCLEvent {
+    pub(crate) fn new_publisher(
+        publisher: Arc<RCLPublisher>,
+        event_type: PublisherEventType,
+    ) -> RCLResult<Self> {
+        let mut event = Box::new(rcl::MTSafeFn::rcl_get_zero_initialized_event());
+
+        {
+            let guard = rcl::MT_UNSAFE_FN.lock();
+            guard.rcl_publisher_event_init(
+                event.as_mut(),
+                &publisher.publisher,
+                event_type.to_rcl(),
+            )?;
+        }
+
+        Ok(RCLEvent {
+            event,
+            event_type: EventType::Publisher(event_type),
+            node: publisher.node.clone(),
+            _publisher: Some(publisher),
+            _subscription: None,
+        })
+    }
+
+    pub(crate) fn new_subscription(
+        subscription: Arc<RCLSubscription>,
+        event_type: SubscriberEventType,
+    ) -> RCLResult<Self> {
+        let mut event = Box::new(rcl::MTSafeFn::rcl_get_zero_initialized_event());
+
+        {
+            let guard = rcl::MT_UNSAFE_FN.lock();
+            guard.rcl_subscription_event_init(
+                event.as_mut(),
+                subscription.subscription.as_ref(),
+                event_type.to_rcl(),
+            )?;
+        }
+
+        Ok(RCLEvent {
+            event,
+            event_type: EventType::Subscriber(event_type),
+            node: subscription.node.clone(),
+            _publisher: None,
+            _subscription: Some(subscription),
+        })
+    }
+
+    pub(crate) fn take(&self) -> RCLResult<EventStatus> {
+        let status = match self.event_type {
+            EventType::Publisher(PublisherEventType::OfferedDeadlineMissed) => {
+                let status: rcl::rmw_offered_deadline_missed_status_t = self.take_status()?;
+                EventStatus::OfferedDeadlineMissed((&status).into())
+            }
+            EventType::Publisher(PublisherEventType::LivelinessLost) => {
+                let status: rcl::rmw_liveliness_lost_status_t = self.take_status()?;
+                EventStatus::LivelinessLost((&status).into())
+            }
+            EventType::Publisher(PublisherEventType::OfferedIncompatibleQoS) => {
+                let status: rcl::rmw_offered_qos_incompatible_event_status_t =
+                    self.take_status()?;
+                EventStatus::OfferedIncompatibleQoS((&status).into())
+            }
+            EventType::Subscriber(SubscriberEventType::RequestedDeadlineMissed) => {
+                let status: rcl::rmw_requested_deadline_missed_status_t = self.take_status()?;
+                EventStatus::RequestedDeadlineMissed((&status).into())
+            }
+            EventType::Subscriber(SubscriberEventType::LivelinessChanged) => {
+                let status: rcl::rmw_liveliness_changed_status_t = self.take_status()?;
+                EventStatus::LivelinessChanged((&status).into())
+            }
+            EventType::Subscriber(SubscriberEventType::RequestedIncompatibleQoS) => {
+                let status: rcl::rmw_requested_qos_incompatible_event_status_t =
+                    self.take_status()?;
+                EventStatus::RequestedIncompatibleQoS((&status).into())
+            }
+            EventType::Subscriber(SubscriberEventType::MessageLost) => {
+                let status: rcl::rmw_message_lost_status_t = self.take_status()?;
+                EventStatus::MessageLost((&status).into())
+            }
+        };
+
+        Ok(status)
+    }
+
+    fn take_status<S>(&self) -> RCLResult<S> {
+        let mut status: S = unsafe { MaybeUninit::zeroed().assume_init() };
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        guard.rcl_take_event(self.event.as_ref(), &mut status as *mut S as *mut c_void)?;
+        Ok(status)
+    }
+}
+
+impl Drop for RCLEvent {
+    fn drop(&mut self) {
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        let _ = guard.rcl_event_fini(self.event.as_mut());
+    }
+}
+
+unsafe impl Sync for RCLEvent {}
+unsafe impl Send for RCLEvent {}
+
+/// Event of a publisher or a subscriber.
+pub struct Event {
+    pub(crate) event: Arc<RCLEvent>,
+    _unsync: PhantomUnsync,
+}
+
+impl Event {
+    pub(crate) fn new(event: RCLEvent) -> Self {
+        Event {
+            event: Arc::new(event),
+            _unsync: Default::default(),
+        }
+    }
+
+    /// Take the current status of the event.
+    /// `*_change` fields of the status count changes since the last time the status was taken.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::EventInvalid` if the event is invalid, or
+    /// - `RCLError::EventTakeFailed` if taking the status failed, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn take(&self) -> RCLResult<EventStatus> {
+        self.event.take()
+    }
+
+    /// Wait until the event occurs asynchronously, and take the status of the event.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{
+    ///     logger::Logger, pr_warn,
+    ///     topic::event::{Event, EventStatus},
+    /// };
+    ///
+    /// async fn watch_liveliness(mut event: Event) {
+    ///     let logger = Logger::new("watch_liveliness");
+    ///     while let Ok(status) = event.recv().await {
+    ///         if let EventStatus::LivelinessChanged(status) = status {
+    ///             pr_warn!(logger, "alive publishers = {}", status.alive_count);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `ContextShutdown` if the context has been shut down, or
+    /// - `Signaled` if a signal has been received, or
+    /// - `RCLError` if taking the status failed.
+    pub async fn recv(&mut self) -> Result<EventStatus, DynError> {
+        AsyncEventReceiver {
+            event: &self.event,
+            state: None,
+        }
+        .await
+    }
+}
+
+struct EventState {
+    status: Option<RCLResult<EventStatus>>,
+    waker: Option<Waker>,
+}
+
+/// Future to wait an event.
+struct AsyncEventReceiver<'a> {
+    event: &'a Arc<RCLEvent>,
+    state: Option<Arc<Mutex<EventState>>>,
+}
+
+impl<'a> Future for AsyncEventReceiver<'a> {
+    type Output = Result<EventStatus, DynError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        if is_halt() {
+            return Poll::Ready(Err(Signaled.into()));
+        }
+
+        if !self.event.node.context.is_valid() {
+            return Poll::Ready(Err(ContextShutdown.into()));
+        }
+
+        let this = self.get_mut();
+
+        if let Some(state) = &this.state {
+            // waiting
+            let mut guard = state.lock();
+            if let Some(status) = guard.status.take() {
+                Poll::Ready(status.map_err(|e| e.into()))
+            } else {
+                guard.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        } else {
+            let state = Arc::new(Mutex::new(EventState {
+                status: None,
+                waker: Some(cx.waker().clone()),
+            }));
+            let state_cloned = state.clone();
+            let event = this.event.clone();
+
+            let mut guard = SELECTOR.lock();
+            if let Err(e) = guard.send_command(
+                &this.event.node.context,
+                Command::Event(
+                    this.event.clone(),
+                    Box::new(move || {
+                        let mut guard = state_cloned.lock();
+
+                        // No status is taken when the selector is woken up by a signal
+                        // or shutting down the context,
+                        // and the future checks them after waking up.
+                        let result = match event.take() {
+                            Err(RCLError::EventTakeFailed) => CallbackResult::Ok,
+                            status => {
+                                guard.status = Some(status);
+                                CallbackResult::Remove
+                            }
+                        };
+
+                        if let Some(w) = guard.waker.take() {
+                            w.wake();
+                        }
+                        result
+                    }),
+                ),
+            ) {
+                return Poll::Ready(Err(e));
+            }
+
+            this.state = Some(state);
+            Poll::Pending
+        }
+    }
+}
+
+impl<'a> Drop for AsyncEventReceiver<'a> {
+    fn drop(&mut self) {
+        if let Some(state) = &self.state {
+            let is_waiting = {
+                let guard = state.lock();
+                guard.status.is_none()
+            };
+
+            if is_waiting {
+                let mut guard = SELECTOR.lock();
+                let _ = guard.send_command(
+                    &self.event.node.context,
+                    Command::RemoveEvent(self.event.clone()),
+                );
+            }
+        }
+    }
+}
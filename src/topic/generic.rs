@@ -0,0 +1,288 @@
+//! Publishers and subscribers whose message types are specified at runtime.
+//!
+//! `GenericPublisher` and `GenericSubscriber` take a type name such as `"std_msgs/msg/UInt32"`
+//! instead of a type parameter, and exchange serialized messages.
+//! The type support of the message is loaded from `lib<package>__rosidl_typesupport_c`
+//! dynamically, so the library must be found by the dynamic linker
+//! (e.g. by `LD_LIBRARY_PATH` set up by ROS2's `setup.bash`).
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::{context::Context, RecvResult};
+//!
+//! let ctx = Context::new().unwrap();
+//! let node = ctx
+//!     .create_node("generic_rs", None, Default::default())
+//!     .unwrap();
+//!
+//! // Create a subscriber and a publisher.
+//! let subscriber = node
+//!     .create_generic_subscriber("generic_rs_topic", "std_msgs/msg/UInt32", None,
+//!     #[cfg(not(any(feature = "humble", feature = "galactic")))]
+//!     true
+//! ).unwrap();
+//!
+//! let publisher = node
+//!     .create_generic_publisher("generic_rs_topic", "std_msgs/msg/UInt32", None,
+//!     #[cfg(not(any(feature = "humble", feature = "galactic")))]
+//!     true
+//! ).unwrap();
+//!
+//! // Relay a received message.
+//! if let RecvResult::Ok(msg) = subscriber.try_recv() {
+//!     publisher.send(&msg).unwrap();
+//! }
+//! ```
+
+use super::{publisher::Publisher, serialized::SerializedMessage, subscriber::Subscriber};
+use crate::{
+    error::{DynError, RCLResult},
+    node::Node,
+    qos, rcl, RecvResult,
+};
+use std::{
+    ffi::{CStr, CString},
+    fmt::Display,
+    os::raw::c_void,
+    sync::Arc,
+};
+
+#[cfg(target_os = "macos")]
+const LIBRARY_SUFFIX: &str = ".dylib";
+
+#[cfg(not(target_os = "macos"))]
+const LIBRARY_SUFFIX: &str = ".so";
+
+/// Error of loading the type support of a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeSupportError {
+    /// The type name is not of the form `package/msg/Type`.
+    InvalidTypeName(String),
+
+    /// The type support library could not be loaded.
+    LibraryNotFound { library: String, reason: String },
+
+    /// The type support function was not found in the library.
+    SymbolNotFound { symbol: String, reason: String },
+}
+
+impl Display for TypeSupportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeSupportError::InvalidTypeName(name) => write!(f, "invalid type name: {name}"),
+            TypeSupportError::LibraryNotFound { library, reason } => {
+                write!(f, "failed to load {library}: {reason}")
+            }
+            TypeSupportError::SymbolNotFound { symbol, reason } => {
+                write!(f, "failed to find {symbol}: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TypeSupportError {}
+
+/// Type support of a message loaded from a shared library.
+struct TypeSupportLibrary {
+    type_name: String,
+    handle: *mut c_void,
+    type_support: *const rcl::rosidl_message_type_support_t,
+}
+
+impl TypeSupportLibrary {
+    /// Load the type support of `type_name`, which is
+    /// `package/msg/Type` or `package/Type`.
+    fn load(type_name: &str) -> Result<Self, TypeSupportError> {
+        let (package, interface, name) = split_type_name(type_name)
+            .ok_or_else(|| TypeSupportError::InvalidTypeName(type_name.to_string()))?;
+
+        let library = format!("lib{package}__rosidl_typesupport_c{LIBRARY_SUFFIX}");
+        let library_c = CString::new(library.as_str()).unwrap_or_default();
+        let handle = unsafe { libc::dlopen(library_c.as_ptr(), libc::RTLD_LAZY) };
+        if handle.is_null() {
+            return Err(TypeSupportError::LibraryNotFound {
+                library,
+                reason: dlerror(),
+            });
+        }
+
+        let symbol = format!(
+            "rosidl_typesupport_c__get_message_type_support_handle__{package}__{interface}__{name}"
+        );
+        let symbol_c = CString::new(symbol.as_str()).unwrap_or_default();
+        let func = unsafe { libc::dlsym(handle, symbol_c.as_ptr()) };
+        if func.is_null() {
+            let reason = dlerror();
+            unsafe { libc::dlclose(handle) };
+            return Err(TypeSupportError::SymbolNotFound { symbol, reason });
+        }
+
+        let func: extern "C" fn() -> *const rcl::rosidl_message_type_support_t =
+            unsafe { std::mem::transmute(func) };
+
+        Ok(TypeSupportLibrary {
+            type_name: format!("{package}/{interface}/{name}"),
+            handle,
+            type_support: func(),
+        })
+    }
+}
+
+impl Drop for TypeSupportLibrary {
+    fn drop(&mut self) {
+        unsafe { libc::dlclose(self.handle) };
+    }
+}
+
+unsafe impl Sync for TypeSupportLibrary {}
+unsafe impl Send for TypeSupportLibrary {}
+
+fn split_type_name(type_name: &str) -> Option<(&str, &str, &str)> {
+    let tokens: Vec<&str> = type_name.split('/').collect();
+    let (package, interface, name) = match tokens.as_slice() {
+        [package, name] => (*package, "msg", *name),
+        [package, interface, name] => (*package, *interface, *name),
+        _ => return None,
+    };
+
+    let is_valid =
+        |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if is_valid(package) && is_valid(interface) && is_valid(name) {
+        Some((package, interface, name))
+    } else {
+        None
+    }
+}
+
+fn dlerror() -> String {
+    let err = unsafe { libc::dlerror() };
+    if err.is_null() {
+        "unknown error".to_string()
+    } else {
+        unsafe { CStr::from_ptr(err) }
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+/// Publisher whose message type is specified at runtime.
+pub struct GenericPublisher {
+    // `publisher` must be dropped before `type_support`.
+    publisher: Publisher<()>,
+    type_support: TypeSupportLibrary,
+}
+
+impl GenericPublisher {
+    pub(crate) fn new(
+        node: Arc<Node>,
+        topic_name: &str,
+        type_name: &str,
+        qos: Option<qos::Profile>,
+
+        #[cfg(all(not(feature = "humble"), not(feature = "galactic")))]
+        disable_loaned_message: bool,
+    ) -> Result<Self, DynError> {
+        let type_support = TypeSupportLibrary::load(type_name)?;
+        let publisher = Publisher::new_with_type_support(
+            node,
+            topic_name,
+            type_support.type_support,
+            qos,
+            #[cfg(all(not(feature = "humble"), not(feature = "galactic")))]
+            disable_loaned_message,
+        )?;
+
+        Ok(GenericPublisher {
+            publisher,
+            type_support,
+        })
+    }
+
+    pub fn get_topic_name(&self) -> &str {
+        self.publisher.get_topic_name()
+    }
+
+    /// Get the type name of messages, such as `std_msgs/msg/UInt32`.
+    pub fn get_type_name(&self) -> &str {
+        &self.type_support.type_name
+    }
+
+    /// Get the number of subscribers matched to the publisher.
+    pub fn get_subscription_count(&self) -> RCLResult<usize> {
+        self.publisher.get_subscription_count()
+    }
+
+    /// Send a serialized message.
+    /// `msg` must be a serialized message of the type of the publisher.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::PublisherInvalid` if the publisher is invalid, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn send(&self, msg: &SerializedMessage) -> Result<(), DynError> {
+        self.publisher.send_serialized(msg)
+    }
+}
+
+/// Subscriber whose message type is specified at runtime.
+pub struct GenericSubscriber {
+    // `subscriber` must be dropped before `type_support`.
+    subscriber: Subscriber<()>,
+    type_support: TypeSupportLibrary,
+}
+
+impl GenericSubscriber {
+    pub(crate) fn new(
+        node: Arc<Node>,
+        topic_name: &str,
+        type_name: &str,
+        qos: Option<qos::Profile>,
+
+        #[cfg(all(not(feature = "humble"), not(feature = "galactic")))]
+        disable_loaned_message: bool,
+    ) -> Result<Self, DynError> {
+        let type_support = TypeSupportLibrary::load(type_name)?;
+        let subscriber = Subscriber::new_with_type_support(
+            node,
+            topic_name,
+            type_support.type_support,
+            qos,
+            #[cfg(all(not(feature = "humble"), not(feature = "galactic")))]
+            disable_loaned_message,
+        )?;
+
+        Ok(GenericSubscriber {
+            subscriber,
+            type_support,
+        })
+    }
+
+    pub fn get_topic_name(&self) -> &str {
+        self.subscriber.get_topic_name()
+    }
+
+    /// Get the type name of messages, such as `std_msgs/msg/UInt32`.
+    pub fn get_type_name(&self) -> &str {
+        &self.type_support.type_name
+    }
+
+    /// Get the number of publishers matched to the subscriber.
+    pub fn get_publisher_count(&self) -> RCLResult<usize> {
+        self.subscriber.get_publisher_count()
+    }
+
+    /// Non-blocking receive of a serialized message.
+    /// See `Subscriber::try_recv_serialized`.
+    #[must_use]
+    pub fn try_recv(&self) -> RecvResult<SerializedMessage, ()> {
+        self.subscriber.try_recv_serialized()
+    }
+
+    /// Receive a serialized message asynchronously.
+    /// See `Subscriber::recv_serialized`.
+    pub async fn recv(&mut self) -> Result<SerializedMessage, DynError> {
+        self.subscriber.recv_serialized().await
+    }
+}
@@ -0,0 +1,231 @@
+//! Intra-process communication.
+//!
+//! Publishers and subscribers created by `Node::create_intra_process_publisher` and
+//! `Node::create_intra_process_subscriber` hand messages over in-process
+//! if they belong to the same context,
+//! and they communicate with remote peers through the middleware.
+//! Intra-process subscribers ignore messages sent through the middleware
+//! by intra-process publishers of the same context, which have been handed over in-process,
+//! but they receive messages of other publishers of the same context through the middleware.
+//!
+//! Only `Publisher::send_owned` with exactly one intra-process subscriber matched
+//! moves a message without copying.
+//! If two or more intra-process subscribers are matched,
+//! or if a message is sent by `Publisher::send` or `Publisher::send_loaned`,
+//! the message is copied to each subscriber by serialization and deserialization.
+//! In any case, the message is never passed to the middleware
+//! unless remote subscribers are matched.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::{context::Context, msg::common_interfaces::std_msgs, RecvResult};
+//!
+//! let ctx = Context::new().unwrap();
+//! let node = ctx
+//!     .create_node("intra_process_rs", None, Default::default())
+//!     .unwrap();
+//!
+//! // Create a subscriber and a publisher.
+//! let subscriber = node
+//!     .create_intra_process_subscriber::<std_msgs::msg::UInt32>("intra_process_rs_topic", None,
+//!     #[cfg(not(any(feature = "humble", feature = "galactic")))]
+//!     true
+//! ).unwrap();
+//!
+//! let publisher = node
+//!     .create_intra_process_publisher::<std_msgs::msg::UInt32>("intra_process_rs_topic", None,
+//!     #[cfg(not(any(feature = "humble", feature = "galactic")))]
+//!     true
+//! ).unwrap();
+//!
+//! // Send a message without serialization.
+//! let mut msg = std_msgs::msg::UInt32::new().unwrap();
+//! msg.data = 1234;
+//! publisher.send_owned(msg).unwrap();
+//!
+//! if let RecvResult::Ok(msg) = subscriber.try_recv() {
+//!     assert_eq!(msg.data, 1234);
+//! }
+//! ```
+
+use crate::{
+    context::Context,
+    error::{RCLError, RCLResult},
+    rcl,
+    selector::guard_condition::GuardCondition,
+};
+use parking_lot::Mutex;
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    ffi::CStr,
+    os::raw::{c_char, c_void},
+    sync::{Arc, Weak},
+};
+
+/// A topic is identified by its fully qualified name and its type support.
+pub(crate) type TopicKey = (String, usize);
+
+/// Create the key of a topic from the name returned by
+/// `rcl_publisher_get_topic_name` or `rcl_subscription_get_topic_name`.
+pub(crate) fn topic_key(
+    topic_name: *const c_char,
+    type_support: *const rcl::rosidl_message_type_support_t,
+) -> RCLResult<TopicKey> {
+    if topic_name.is_null() {
+        return Err(RCLError::InvalidArgument);
+    }
+
+    let topic_name = unsafe { CStr::from_ptr(topic_name) }
+        .to_string_lossy()
+        .into_owned();
+
+    Ok((topic_name, type_support as usize))
+}
+
+/// Get the GID of a publisher, which is the same as `MessageInfo::publisher_gid`.
+pub(crate) fn publisher_gid(publisher: &rcl::rcl_publisher_t) -> RCLResult<Vec<u8>> {
+    let handle = rcl::MTSafeFn::rcl_publisher_get_rmw_handle(publisher);
+    if handle.is_null() {
+        return Err(RCLError::PublisherInvalid);
+    }
+
+    let mut gid: rcl::rmw_gid_t = unsafe { std::mem::zeroed() };
+    rcl::MTSafeFn::rmw_get_gid_for_publisher(handle, &mut gid)?;
+    Ok(gid.data.to_vec())
+}
+
+/// Intra-process subscriptions and publishers of a context.
+#[derive(Default)]
+pub(crate) struct IntraProcessManager {
+    queues: Mutex<BTreeMap<TopicKey, Vec<Weak<IntraProcessQueue>>>>,
+
+    // GIDs of intra-process publishers.
+    publishers: Mutex<BTreeSet<Vec<u8>>>,
+}
+
+impl IntraProcessManager {
+    pub(crate) fn register(&self, key: TopicKey, queue: &Arc<IntraProcessQueue>) {
+        let mut guard = self.queues.lock();
+        guard.entry(key).or_default().push(Arc::downgrade(queue));
+    }
+
+    /// Get the queues of the intra-process subscriptions of `key`.
+    /// Queues of dropped subscriptions are removed.
+    pub(crate) fn get_queues(&self, key: &TopicKey) -> Vec<Arc<IntraProcessQueue>> {
+        let mut guard = self.queues.lock();
+        let Some(queues) = guard.get_mut(key) else {
+            return Vec::new();
+        };
+
+        queues.retain(|q| q.strong_count() > 0);
+        let result: Vec<_> = queues.iter().filter_map(|q| q.upgrade()).collect();
+
+        if queues.is_empty() {
+            guard.remove(key);
+        }
+
+        result
+    }
+
+    pub(crate) fn register_publisher(&self, gid: Vec<u8>) {
+        self.publishers.lock().insert(gid);
+    }
+
+    pub(crate) fn unregister_publisher(&self, gid: &[u8]) {
+        self.publishers.lock().remove(gid);
+    }
+
+    /// Check whether `gid` is of an intra-process publisher,
+    /// whose messages are handed over to intra-process subscribers in-process.
+    pub(crate) fn is_publisher(&self, gid: &[u8]) -> bool {
+        self.publishers.lock().contains(gid)
+    }
+}
+
+/// A type-erased message.
+/// The type is guaranteed by the type support of the topic.
+struct IntraProcessMessage {
+    ptr: *mut c_void,
+    drop_fn: unsafe fn(*mut c_void),
+}
+
+impl IntraProcessMessage {
+    fn new<T>(msg: T) -> Self {
+        unsafe fn drop_box<T>(ptr: *mut c_void) {
+            drop(Box::from_raw(ptr as *mut T));
+        }
+
+        IntraProcessMessage {
+            ptr: Box::into_raw(Box::new(msg)) as *mut c_void,
+            drop_fn: drop_box::<T>,
+        }
+    }
+
+    /// `T` must be the type given to `new`.
+    unsafe fn into_inner<T>(self) -> T {
+        let ptr = self.ptr as *mut T;
+        std::mem::forget(self);
+        *Box::from_raw(ptr)
+    }
+}
+
+impl Drop for IntraProcessMessage {
+    fn drop(&mut self) {
+        unsafe { (self.drop_fn)(self.ptr) };
+    }
+}
+
+unsafe impl Send for IntraProcessMessage {}
+
+/// Queue of messages handed over to an intra-process subscription.
+pub(crate) struct IntraProcessQueue {
+    messages: Mutex<VecDeque<IntraProcessMessage>>,
+
+    // `None` if the history policy is `KeepAll`.
+    depth: Option<usize>,
+
+    // Triggered when messages are queued to wake selectors up.
+    pub(crate) cond: GuardCondition,
+}
+
+impl IntraProcessQueue {
+    pub(crate) fn new(context: Arc<Context>, depth: Option<usize>) -> RCLResult<Self> {
+        Ok(IntraProcessQueue {
+            messages: Mutex::new(VecDeque::new()),
+            depth: depth.map(|d| d.max(1)),
+            cond: GuardCondition::new(context)?,
+        })
+    }
+
+    /// `T` must be the type of the topic.
+    pub(crate) fn push<T>(&self, msg: T) -> RCLResult<()> {
+        {
+            let mut guard = self.messages.lock();
+            if let Some(depth) = self.depth {
+                while guard.len() >= depth {
+                    guard.pop_front();
+                }
+            }
+            guard.push_back(IntraProcessMessage::new(msg));
+        }
+
+        self.cond.trigger()
+    }
+
+    /// `T` must be the type of the topic.
+    pub(crate) fn pop<T>(&self) -> Option<T> {
+        let (msg, is_remaining) = {
+            let mut guard = self.messages.lock();
+            let msg = guard.pop_front()?;
+            (msg, !guard.is_empty())
+        };
+
+        if is_remaining {
+            // wake selectors up again because the guard condition is reset by waiting
+            let _ = self.cond.trigger();
+        }
+
+        Some(unsafe { msg.into_inner() })
+    }
+}
@@ -52,15 +52,20 @@
 //! `None` of the 2nd argument of `create_publisher` is equivalent to `Some(Profile::default())`.
 
 use crate::{
-    error::{DynError, RCLResult},
+    error::{DynError, RCLError, RCLResult},
     get_allocator,
     msg::TypeSupport,
     node::Node,
     publisher_loaned_message::PublisherLoanedMessage,
     qos, rcl,
     signal_handler::Signaled,
+    topic::{
+        event::{Event, PublisherEventType, RCLEvent},
+        intra_process::{publisher_gid, topic_key, IntraProcessQueue, TopicKey},
+        serialized::{deserialize, deserialize_with_type_support, serialize, SerializedMessage},
+    },
 };
-use std::{ffi::CString, marker::PhantomData, ptr::null_mut, sync::Arc};
+use std::{ffi::CString, marker::PhantomData, ptr::null_mut, sync::Arc, time::Duration};
 
 #[cfg(feature = "rcl_stat")]
 use crate::helper::statistics::{SerializableTimeStat, TimeStatistics};
@@ -68,6 +73,22 @@ use crate::helper::statistics::{SerializableTimeStat, TimeStatistics};
 #[cfg(feature = "rcl_stat")]
 use parking_lot::Mutex;
 
+pub(crate) struct RCLPublisher {
+    pub publisher: rcl::rcl_publisher_t,
+    pub node: Arc<Node>,
+}
+
+impl Drop for RCLPublisher {
+    fn drop(&mut self) {
+        let (node, publisher) = (&mut self.node, &mut self.publisher);
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        let _ = guard.rcl_publisher_fini(publisher, unsafe { node.as_ptr_mut() });
+    }
+}
+
+unsafe impl Sync for RCLPublisher {}
+unsafe impl Send for RCLPublisher {}
+
 /// Publisher.
 ///
 /// # Example
@@ -93,7 +114,7 @@ use parking_lot::Mutex;
 /// publisher.send(&msg).unwrap();
 /// ```
 pub struct Publisher<T> {
-    publisher: Arc<rcl::rcl_publisher_t>,
+    pub(crate) publisher: Arc<RCLPublisher>,
     topic_name: String,
 
     #[cfg(feature = "rcl_stat")]
@@ -101,6 +122,13 @@ pub struct Publisher<T> {
 
     _phantom: PhantomData<T>,
     node: Arc<Node>,
+
+    // `Some` if the publisher hands messages over to intra-process subscribers.
+    intra_process: Option<TopicKey>,
+
+    // GID registered to the context to make intra-process subscribers
+    // ignore messages of the publisher sent through the middleware.
+    intra_process_gid: Option<Vec<u8>>,
 }
 
 impl<T: TypeSupport> Publisher<T> {
@@ -112,45 +140,48 @@ impl<T: TypeSupport> Publisher<T> {
         #[cfg(all(not(feature = "humble"), not(feature = "galactic")))]
         disable_loaned_message: bool,
     ) -> RCLResult<Self> {
-        let mut publisher = rcl::MTSafeFn::rcl_get_zero_initialized_publisher();
-
-        let topic_name_c = CString::new(topic_name).unwrap_or_default();
+        Self::new_with_type_support(
+            node,
+            topic_name,
+            T::type_support(),
+            qos,
+            #[cfg(all(not(feature = "humble"), not(feature = "galactic")))]
+            disable_loaned_message,
+        )
+    }
 
-        #[cfg(any(feature = "humble", feature = "galactic"))]
-        let options = Options::new(&qos.unwrap_or_default());
+    pub(crate) fn new_intra_process(
+        node: Arc<Node>,
+        topic_name: &str,
+        qos: Option<qos::Profile>,
 
         #[cfg(all(not(feature = "humble"), not(feature = "galactic")))]
-        let options = Options::new(&qos.unwrap_or_default(), disable_loaned_message);
-
-        {
-            let guard = rcl::MT_UNSAFE_FN.lock();
-            guard.rcl_publisher_init(
-                &mut publisher,
-                node.as_ptr(),
-                T::type_support(),
-                topic_name_c.as_ptr(),
-                options.as_ptr(),
-            )?;
-        }
-
-        Ok(Publisher {
-            publisher: Arc::new(publisher),
+        disable_loaned_message: bool,
+    ) -> RCLResult<Self> {
+        let mut publisher = Self::new(
             node,
-            topic_name: topic_name.to_string(),
-
-            #[cfg(feature = "rcl_stat")]
-            latency_publish: Mutex::new(TimeStatistics::new()),
+            topic_name,
+            qos,
+            #[cfg(all(not(feature = "humble"), not(feature = "galactic")))]
+            disable_loaned_message,
+        )?;
 
-            _phantom: Default::default(),
-        })
-    }
+        let key = {
+            let guard = rcl::MT_UNSAFE_FN.lock();
+            let name = guard.rcl_publisher_get_topic_name(&publisher.publisher.publisher);
+            topic_key(name, T::type_support())?
+        };
+        publisher.intra_process = Some(key);
 
-    pub fn get_topic_name(&self) -> &str {
-        &self.topic_name
-    }
+        let gid = publisher_gid(&publisher.publisher.publisher)?;
+        publisher
+            .node
+            .context
+            .intra_process
+            .register_publisher(gid.clone());
+        publisher.intra_process_gid = Some(gid);
 
-    pub fn can_loan_messages(&self) -> bool {
-        rcl::MTSafeFn::rcl_publisher_can_loan_messages(self.publisher.as_ref())
+        Ok(publisher)
     }
 
     /// Borrows a memory chunk from the shared memory.
@@ -160,6 +191,10 @@ impl<T: TypeSupport> Publisher<T> {
 
     /// Send a message.
     ///
+    /// If the publisher is an intra-process publisher,
+    /// the message is copied to intra-process subscribers by serialization.
+    /// Use `send_owned` to hand the message over to a single subscriber without copying.
+    ///
     /// # Example
     ///
     /// ```
@@ -191,11 +226,68 @@ impl<T: TypeSupport> Publisher<T> {
             return Err(Signaled.into());
         }
 
+        let queues = self.get_intra_process_queues();
+        if !queues.is_empty() {
+            let serialized = serialize(msg)?;
+            for queue in queues.iter() {
+                queue.push(deserialize::<T>(&serialized)?)?;
+            }
+
+            if !self.has_remote_subscribers(queues.len())? {
+                return Ok(());
+            }
+        }
+
+        self.publish(msg)
+    }
+
+    /// Send a message by taking the ownership.
+    ///
+    /// If the publisher is an intra-process publisher and
+    /// only one intra-process subscriber is matched,
+    /// the message is moved to the subscriber without copying.
+    /// If two or more intra-process subscribers are matched,
+    /// the last one takes the message and the others receive copies made by serialization.
+    /// See `topic::intra_process`.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::PublisherInvalid` if the publisher is invalid, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn send_owned(&self, msg: T) -> Result<(), DynError> {
+        if crate::is_halt() {
+            return Err(Signaled.into());
+        }
+
+        let mut queues = self.get_intra_process_queues();
+        let Some(last) = queues.pop() else {
+            return self.publish(&msg);
+        };
+
+        if self.has_remote_subscribers(queues.len() + 1)? {
+            self.publish(&msg)?;
+        }
+
+        if !queues.is_empty() {
+            let serialized = serialize(&msg)?;
+            for queue in queues.iter() {
+                queue.push(deserialize::<T>(&serialized)?)?;
+            }
+        }
+
+        // the last subscriber takes the original
+        last.push(msg)?;
+
+        Ok(())
+    }
+
+    fn publish(&self, msg: &T) -> Result<(), DynError> {
         #[cfg(feature = "rcl_stat")]
         let start = std::time::SystemTime::now();
 
         if let Err(e) =
-            rcl::MTSafeFn::rcl_publish(self.publisher.as_ref(), msg as *const T as _, null_mut())
+            rcl::MTSafeFn::rcl_publish(&self.publisher.publisher, msg as *const T as _, null_mut())
         {
             return Err(e.into());
         }
@@ -214,11 +306,28 @@ impl<T: TypeSupport> Publisher<T> {
     /// Send a loaned message.
     ///
     /// This functions takes the ownership of the loaned message since its chunk should be transferred back to the middleware.
+    ///
+    /// If the publisher is an intra-process publisher,
+    /// the message is copied to intra-process subscribers by serialization,
+    /// and the chunk is returned to the middleware without being published
+    /// unless remote subscribers are matched.
     pub fn send_loaned(&self, msg: PublisherLoanedMessage<T>) -> Result<(), DynError> {
         if crate::is_halt() {
             return Err(Signaled.into());
         }
 
+        let queues = self.get_intra_process_queues();
+        if !queues.is_empty() {
+            let serialized = serialize(&*msg)?;
+            for queue in queues.iter() {
+                queue.push(deserialize::<T>(&serialized)?)?;
+            }
+
+            if !self.has_remote_subscribers(queues.len())? {
+                return Ok(());
+            }
+        }
+
         #[cfg(feature = "rcl_stat")]
         let start = std::time::SystemTime::now();
 
@@ -234,6 +343,280 @@ impl<T: TypeSupport> Publisher<T> {
 
         Ok(())
     }
+}
+
+impl<T> Publisher<T> {
+    pub(crate) fn new_with_type_support(
+        node: Arc<Node>,
+        topic_name: &str,
+        type_support: *const rcl::rosidl_message_type_support_t,
+        qos: Option<qos::Profile>,
+
+        #[cfg(all(not(feature = "humble"), not(feature = "galactic")))]
+        disable_loaned_message: bool,
+    ) -> RCLResult<Self> {
+        let mut publisher = rcl::MTSafeFn::rcl_get_zero_initialized_publisher();
+
+        let topic_name_c = CString::new(topic_name).unwrap_or_default();
+
+        #[cfg(any(feature = "humble", feature = "galactic"))]
+        let options = Options::new(&qos.unwrap_or_default());
+
+        #[cfg(all(not(feature = "humble"), not(feature = "galactic")))]
+        let options = Options::new(&qos.unwrap_or_default(), disable_loaned_message);
+
+        {
+            let guard = rcl::MT_UNSAFE_FN.lock();
+            guard.rcl_publisher_init(
+                &mut publisher,
+                node.as_ptr(),
+                type_support,
+                topic_name_c.as_ptr(),
+                options.as_ptr(),
+            )?;
+        }
+
+        Ok(Publisher {
+            publisher: Arc::new(RCLPublisher {
+                publisher,
+                node: node.clone(),
+            }),
+            node,
+            topic_name: topic_name.to_string(),
+
+            #[cfg(feature = "rcl_stat")]
+            latency_publish: Mutex::new(TimeStatistics::new()),
+
+            _phantom: Default::default(),
+            intra_process: None,
+            intra_process_gid: None,
+        })
+    }
+
+    pub fn get_topic_name(&self) -> &str {
+        &self.topic_name
+    }
+
+    /// Check whether the publisher hands messages over to intra-process subscribers.
+    pub fn is_intra_process(&self) -> bool {
+        self.intra_process.is_some()
+    }
+
+    fn get_intra_process_queues(&self) -> Vec<Arc<IntraProcessQueue>> {
+        match &self.intra_process {
+            Some(key) => self.node.context.intra_process.get_queues(key),
+            None => Vec::new(),
+        }
+    }
+
+    /// Intra-process subscribers are matched as well as remote subscribers,
+    /// so remote subscribers exist if more subscribers than `num_intra_process` are matched.
+    fn has_remote_subscribers(&self, num_intra_process: usize) -> RCLResult<bool> {
+        Ok(self.get_subscription_count()? > num_intra_process)
+    }
+
+    /// Get the number of subscribers matched to the publisher.
+    pub fn get_subscription_count(&self) -> RCLResult<usize> {
+        let mut count = 0;
+        rcl::MTSafeFn::rcl_publisher_get_subscription_count(&self.publisher.publisher, &mut count)?;
+
+        #[allow(clippy::unnecessary_cast)] // size_t is not usize on galactic
+        Ok(count as usize)
+    }
+
+    /// Get the QoS profile actually used by the middleware.
+    ///
+    /// `SystemDefault` values of the profile specified when creating the publisher
+    /// are replaced by the values chosen by the middleware.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::PublisherInvalid` if the publisher is invalid.
+    pub fn get_actual_qos(&self) -> RCLResult<qos::Profile> {
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        qos::Profile::from_actual_qos(
+            guard.rcl_publisher_get_actual_qos(&self.publisher.publisher),
+            RCLError::PublisherInvalid,
+        )
+    }
+
+    /// Block until at least `n` subscribers are matched to the publisher or `timeout` elapses.
+    /// This returns `false` if timed out.
+    /// Messages sent before subscribers are matched are not delivered to them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{msg::common_interfaces::std_msgs, topic::publisher::Publisher};
+    /// use std::time::Duration;
+    ///
+    /// fn send_first_message(publisher: &Publisher<std_msgs::msg::Empty>) {
+    ///     if publisher.wait_for_subscribers(1, Duration::from_secs(1)).unwrap() {
+    ///         let msg = std_msgs::msg::Empty::new().unwrap();
+    ///         publisher.send(&msg).unwrap();
+    ///     }
+    /// }
+    /// ```
+    pub fn wait_for_subscribers(&self, n: usize, timeout: Duration) -> Result<bool, DynError> {
+        self.node
+            .wait_graph(timeout, || Ok(self.get_subscription_count()? >= n))
+    }
+
+    /// Wait until at least `n` subscribers are matched to the publisher asynchronously.
+    pub async fn wait_for_subscribers_async(&self, n: usize) -> Result<(), DynError> {
+        self.node
+            .clone()
+            .wait_graph_async(|| Ok(self.get_subscription_count()? >= n))
+            .await
+    }
+
+    /// Block until all sent messages are acknowledged by the matched subscribers or `timeout` elapses.
+    /// This returns `false` if timed out.
+    /// If the reliability policy of the publisher is not `Reliable`, this returns `true` immediately.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{msg::common_interfaces::std_msgs, topic::publisher::Publisher};
+    /// use std::time::Duration;
+    ///
+    /// fn send_last_message(publisher: Publisher<std_msgs::msg::Empty>) {
+    ///     let msg = std_msgs::msg::Empty::new().unwrap();
+    ///     publisher.send(&msg).unwrap();
+    ///
+    ///     // Make sure the message is delivered before dropping the publisher.
+    ///     #[cfg(any(feature = "humble", feature = "iron"))]
+    ///     publisher.wait_for_all_acked(Duration::from_secs(1)).unwrap();
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::PublisherInvalid` if the publisher is invalid, or
+    /// - `RCLError::Unsupported` if the middleware does not support this feature, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    #[cfg(any(feature = "humble", feature = "iron"))]
+    pub fn wait_for_all_acked(&self, timeout: Duration) -> RCLResult<bool> {
+        let timeout = timeout.as_nanos().min(i64::MAX as u128) as rcl::rcl_duration_value_t;
+        match rcl::MTSafeFn::rcl_publisher_wait_for_all_acked(&self.publisher.publisher, timeout) {
+            Ok(()) => Ok(true),
+            Err(RCLError::Timeout) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Manually assert that the publisher is alive.
+    /// This is required if the liveliness policy is `LivelinessPolicy::ManualByTopic`,
+    /// otherwise subscribers regard the publisher as not alive after the lease duration.
+    /// Sending a message also asserts the liveliness.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::PublisherInvalid` if the publisher is invalid, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn assert_liveliness(&self) -> RCLResult<()> {
+        rcl::MTSafeFn::rcl_publisher_assert_liveliness(&self.publisher.publisher)
+    }
+
+    pub fn can_loan_messages(&self) -> bool {
+        rcl::MTSafeFn::rcl_publisher_can_loan_messages(&self.publisher.publisher)
+    }
+
+    /// Create a QoS event of the publisher.
+    /// See `topic::event`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{
+    ///     msg::common_interfaces::std_msgs,
+    ///     topic::{event::{Event, PublisherEventType}, publisher::Publisher},
+    /// };
+    ///
+    /// fn create_event(publisher: &Publisher<std_msgs::msg::Empty>) -> Event {
+    ///     publisher
+    ///         .create_event(PublisherEventType::OfferedIncompatibleQoS)
+    ///         .unwrap()
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::BadAlloc` if allocating memory failed, or
+    /// - `RCLError::Unsupported` if the event type is not supported by the middleware, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn create_event(&self, event_type: PublisherEventType) -> RCLResult<Event> {
+        let event = RCLEvent::new_publisher(self.publisher.clone(), event_type)?;
+        Ok(Event::new(event))
+    }
+
+    /// Send a serialized message.
+    /// `msg` must be a serialized message of `T`,
+    /// which is created by `topic::serialized::serialize` or received by `Subscriber::try_recv_serialized`, for example.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{
+    ///     msg::common_interfaces::std_msgs,
+    ///     topic::{publisher::Publisher, serialized::serialize},
+    /// };
+    ///
+    /// fn send_serialized(publisher: &Publisher<std_msgs::msg::UInt32>) {
+    ///     let mut msg = std_msgs::msg::UInt32::new().unwrap();
+    ///     msg.data = 1234;
+    ///
+    ///     let serialized = serialize(&msg).unwrap();
+    ///     publisher.send_serialized(&serialized).unwrap();
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::PublisherInvalid` if the publisher is invalid, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn send_serialized(&self, msg: &SerializedMessage) -> Result<(), DynError> {
+        if crate::is_halt() {
+            return Err(Signaled.into());
+        }
+
+        if let Some((_, type_support)) = &self.intra_process {
+            let queues = self.get_intra_process_queues();
+            if !queues.is_empty() {
+                for queue in queues.iter() {
+                    queue.push(deserialize_with_type_support::<T>(
+                        msg,
+                        *type_support as *const rcl::rosidl_message_type_support_t,
+                    )?)?;
+                }
+
+                if !self.has_remote_subscribers(queues.len())? {
+                    return Ok(());
+                }
+            }
+        }
+
+        #[cfg(feature = "rcl_stat")]
+        let start = std::time::SystemTime::now();
+
+        rcl::MTSafeFn::rcl_publish_serialized_message(
+            &self.publisher.publisher,
+            msg.as_ptr(),
+            null_mut(),
+        )?;
+
+        #[cfg(feature = "rcl_stat")]
+        {
+            if let Ok(dur) = start.elapsed() {
+                let mut guard = self.latency_publish.lock();
+                guard.add(dur);
+            }
+        }
+
+        Ok(())
+    }
 
     /// Get latency statistics information of `rcl_publish()`.
     #[cfg(feature = "rcl_stat")]
@@ -243,16 +626,6 @@ impl<T: TypeSupport> Publisher<T> {
     }
 }
 
-impl<T> Drop for Publisher<T> {
-    fn drop(&mut self) {
-        let (node, publisher) = (&mut self.node, &mut self.publisher);
-        let guard = rcl::MT_UNSAFE_FN.lock();
-        let _ = guard.rcl_publisher_fini(publisher.as_ref() as *const _ as *mut _, unsafe {
-            node.as_ptr_mut()
-        });
-    }
-}
-
 /// Options for publishers.
 struct Options {
     options: rcl::rcl_publisher_options_t,
@@ -280,5 +653,13 @@ impl Options {
     }
 }
 
+impl<T> Drop for Publisher<T> {
+    fn drop(&mut self) {
+        if let Some(gid) = &self.intra_process_gid {
+            self.node.context.intra_process.unregister_publisher(gid);
+        }
+    }
+}
+
 unsafe impl<T> Sync for Publisher<T> {}
 unsafe impl<T> Send for Publisher<T> {}
@@ -0,0 +1,168 @@
+//! Serialized messages.
+//!
+//! `SerializedMessage` holds a message as raw bytes encoded by the middleware (CDR).
+//! It is useful to record, relay, or bridge topics without converting messages to typed structures.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::{
+//!     msg::common_interfaces::std_msgs,
+//!     topic::serialized::{deserialize, serialize},
+//! };
+//!
+//! let mut msg = std_msgs::msg::UInt32::new().unwrap();
+//! msg.data = 1234;
+//!
+//! // Serialize the message.
+//! let serialized = serialize(&msg).unwrap();
+//! println!("bytes = {:?}", serialized.as_bytes());
+//!
+//! // Deserialize the message.
+//! let msg: std_msgs::msg::UInt32 = deserialize(&serialized).unwrap();
+//! assert_eq!(msg.data, 1234);
+//! ```
+
+use crate::{error::RCLResult, get_allocator, msg::TypeSupport, rcl};
+use std::{mem::MaybeUninit, os::raw::c_void};
+
+/// A message serialized by the middleware.
+pub struct SerializedMessage {
+    msg: rcl::rcl_serialized_message_t,
+}
+
+impl SerializedMessage {
+    /// Create an empty serialized message.
+    pub fn new() -> RCLResult<Self> {
+        Self::with_capacity(0)
+    }
+
+    /// Create an empty serialized message whose buffer can hold `capacity` bytes.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::BadAlloc` if allocating memory failed.
+    pub fn with_capacity(capacity: usize) -> RCLResult<Self> {
+        let mut msg = rcl::MTSafeFn::rcutils_get_zero_initialized_uint8_array();
+
+        #[allow(clippy::unnecessary_cast)] // size_t is not usize on galactic
+        rcl::MTSafeFn::rcutils_uint8_array_init(
+            &mut msg,
+            capacity as rcl::size_t,
+            &get_allocator(),
+        )?;
+
+        Ok(SerializedMessage { msg })
+    }
+
+    /// Create a serialized message by copying `bytes`.
+    /// `bytes` must be encoded by the middleware, such as bytes returned by `SerializedMessage::as_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> RCLResult<Self> {
+        let mut result = Self::with_capacity(bytes.len())?;
+        if !bytes.is_empty() {
+            unsafe {
+                std::ptr::copy_nonoverlapping(bytes.as_ptr(), result.msg.buffer, bytes.len())
+            };
+        }
+
+        #[allow(clippy::unnecessary_cast)] // size_t is not usize on galactic
+        {
+            result.msg.buffer_length = bytes.len() as rcl::size_t;
+        }
+
+        Ok(result)
+    }
+
+    /// Get the serialized bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        if self.msg.buffer.is_null() {
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(self.msg.buffer, self.len()) }
+        }
+    }
+
+    /// Get the length of the serialized bytes.
+    pub fn len(&self) -> usize {
+        #[allow(clippy::unnecessary_cast)] // size_t is not usize on galactic
+        {
+            self.msg.buffer_length as usize
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the capacity of the buffer.
+    pub fn capacity(&self) -> usize {
+        #[allow(clippy::unnecessary_cast)] // size_t is not usize on galactic
+        {
+            self.msg.buffer_capacity as usize
+        }
+    }
+
+    pub(crate) fn as_ptr(&self) -> *const rcl::rcl_serialized_message_t {
+        &self.msg
+    }
+
+    pub(crate) fn as_ptr_mut(&mut self) -> *mut rcl::rcl_serialized_message_t {
+        &mut self.msg
+    }
+}
+
+impl Drop for SerializedMessage {
+    fn drop(&mut self) {
+        let _ = rcl::MTSafeFn::rcutils_uint8_array_fini(&mut self.msg);
+    }
+}
+
+unsafe impl Sync for SerializedMessage {}
+unsafe impl Send for SerializedMessage {}
+
+/// Serialize a message by `rmw_serialize`.
+///
+/// # Errors
+///
+/// - `RCLError::BadAlloc` if allocating memory failed, or
+/// - `RCLError::Error` if an unspecified error occurs.
+pub fn serialize<T: TypeSupport>(msg: &T) -> RCLResult<SerializedMessage> {
+    let mut result = SerializedMessage::new()?;
+
+    let guard = rcl::MT_UNSAFE_FN.lock();
+    guard.rmw_serialize(
+        msg as *const T as *const c_void,
+        T::type_support(),
+        result.as_ptr_mut(),
+    )?;
+
+    Ok(result)
+}
+
+/// Deserialize a message by `rmw_deserialize`.
+/// `msg` must be a serialized message of `T`.
+///
+/// # Errors
+///
+/// - `RCLError::BadAlloc` if allocating memory failed, or
+/// - `RCLError::Error` if an unspecified error occurs.
+pub fn deserialize<T: TypeSupport>(msg: &SerializedMessage) -> RCLResult<T> {
+    deserialize_with_type_support(msg, T::type_support())
+}
+
+/// `type_support` must be the type support of `T`.
+pub(crate) fn deserialize_with_type_support<T>(
+    msg: &SerializedMessage,
+    type_support: *const rcl::rosidl_message_type_support_t,
+) -> RCLResult<T> {
+    let mut ros_message: T = unsafe { MaybeUninit::zeroed().assume_init() };
+
+    let guard = rcl::MT_UNSAFE_FN.lock();
+    guard.rmw_deserialize(
+        msg.as_ptr(),
+        type_support,
+        &mut ros_message as *mut T as *mut c_void,
+    )?;
+
+    Ok(ros_message)
+}
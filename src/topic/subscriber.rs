@@ -162,6 +162,7 @@
 //! `None` of the 2nd argument of `create_subscriber` is equivalent to `Some(Profile::default())`.
 
 use crate::{
+    context::ContextShutdown,
     error::{DynError, RCLError, RCLResult},
     get_allocator, is_halt,
     msg::TypeSupport,
@@ -173,8 +174,14 @@ use crate::{
     },
     signal_handler::Signaled,
     subscriber_loaned_message::SubscriberLoanedMessage,
+    topic::{
+        event::{Event, RCLEvent, SubscriberEventType},
+        intra_process::{topic_key, IntraProcessQueue},
+        serialized::SerializedMessage,
+    },
     PhantomUnsync, RecvResult,
 };
+use futures_core::{stream::FusedStream, Stream};
 use pin_project::{pin_project, pinned_drop};
 use std::{
     ffi::CString,
@@ -188,6 +195,9 @@ use std::{
     task::{self, Poll},
 };
 
+#[cfg(not(any(feature = "humble", feature = "galactic")))]
+use std::{ffi::CStr, os::raw::c_char};
+
 #[cfg(feature = "rcl_stat")]
 use crate::helper::statistics::{SerializableTimeStat, TimeStatistics};
 
@@ -199,6 +209,9 @@ pub(crate) struct RCLSubscription {
 
     topic_name: String,
 
+    // `Some` if the subscription receives messages from intra-process publishers.
+    pub intra_process: Option<Arc<IntraProcessQueue>>,
+
     #[cfg(feature = "rcl_stat")]
     pub latency_take: Mutex<TimeStatistics<4096>>,
     pub node: Arc<Node>,
@@ -241,44 +254,82 @@ impl<T: TypeSupport> Subscriber<T> {
         #[cfg(all(not(feature = "humble"), not(feature = "galactic")))]
         disable_loaned_massage: bool,
     ) -> RCLResult<Self> {
-        let mut subscription = Box::new(rcl::MTSafeFn::rcl_get_zero_initialized_subscription());
+        Self::new_with_type_support(
+            node,
+            topic_name,
+            T::type_support(),
+            qos,
+            #[cfg(all(not(feature = "humble"), not(feature = "galactic")))]
+            disable_loaned_massage,
+        )
+    }
 
-        let topic_name_c = CString::new(topic_name).unwrap_or_default();
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub(crate) fn new_with_filter(
+        node: Arc<Node>,
+        topic_name: &str,
+        qos: Option<qos::Profile>,
+        expression: &str,
+        parameters: &[&str],
+        disable_loaned_massage: bool,
+    ) -> RCLResult<Self> {
+        let mut options = Options::new(&qos.unwrap_or_default(), disable_loaned_massage);
+        options.set_content_filter(expression, parameters)?;
 
-        #[cfg(any(feature = "humble", feature = "galactic"))]
-        let options = Options::new(&qos.unwrap_or_default());
+        let subscriber =
+            Self::new_with_options(node, topic_name, T::type_support(), &options, None)?;
+
+        // The middleware silently ignores the filter if it does not support content filtered topics.
+        if subscriber.is_content_filter_enabled() {
+            Ok(subscriber)
+        } else {
+            Err(RCLError::Unsupported)
+        }
+    }
+
+    pub(crate) fn new_intra_process(
+        node: Arc<Node>,
+        topic_name: &str,
+        qos: Option<qos::Profile>,
 
         #[cfg(all(not(feature = "humble"), not(feature = "galactic")))]
-        let options = Options::new(&qos.unwrap_or_default(), disable_loaned_massage);
+        disable_loaned_massage: bool,
+    ) -> RCLResult<Self> {
+        let qos = qos.unwrap_or_default();
+        let depth = match qos.history {
+            qos::policy::HistoryPolicy::KeepAll => None,
+            _ => Some(qos.depth),
+        };
 
-        {
-            let guard = rcl::MT_UNSAFE_FN.lock();
+        #[cfg(any(feature = "humble", feature = "galactic"))]
+        let options = Options::new(&qos);
 
-            guard.rcl_subscription_init(
-                subscription.as_mut(),
-                node.as_ptr(),
-                T::type_support(),
-                topic_name_c.as_ptr(),
-                options.as_ptr(),
-            )?;
-        }
+        #[cfg(all(not(feature = "humble"), not(feature = "galactic")))]
+        let options = Options::new(&qos, disable_loaned_massage);
 
-        Ok(Subscriber {
-            subscription: Arc::new(RCLSubscription {
-                subscription,
-                node,
-                topic_name: topic_name.to_string(),
+        let queue = Arc::new(IntraProcessQueue::new(node.context.clone(), depth)?);
+        let subscriber = Self::new_with_options(
+            node,
+            topic_name,
+            T::type_support(),
+            &options,
+            Some(queue.clone()),
+        )?;
 
-                #[cfg(feature = "rcl_stat")]
-                latency_take: Mutex::new(TimeStatistics::new()),
-            }),
-            _phantom: Default::default(),
-            _unsync: Default::default(),
-        })
-    }
+        let key = {
+            let guard = rcl::MT_UNSAFE_FN.lock();
+            let name = guard
+                .rcl_subscription_get_topic_name(subscriber.subscription.subscription.as_ref());
+            topic_key(name, T::type_support())?
+        };
+        subscriber
+            .subscription
+            .node
+            .context
+            .intra_process
+            .register(key, &queue);
 
-    pub fn get_topic_name(&self) -> &str {
-        &self.subscription.topic_name
+        Ok(subscriber)
     }
 
     /// Non-blocking receive.
@@ -334,6 +385,64 @@ impl<T: TypeSupport> Subscriber<T> {
         }
     }
 
+    /// Non-blocking receive of at most `max` messages.
+    ///
+    /// Messages are taken by a single `rcl::rcl_take_sequence` call
+    /// if the middleware supports it,
+    /// so this reduces the overhead of taking messages of high-rate topics.
+    /// `try_recv_many()` returns `RecvResult::RetryLater` if
+    /// no message is available.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{
+    ///     logger::Logger, msg::common_interfaces::std_msgs, pr_error, pr_info,
+    ///     topic::subscriber::Subscriber, RecvResult,
+    /// };
+    ///
+    /// fn pubsub(subscriber: Subscriber<std_msgs::msg::UInt32>, logger: Logger) {
+    ///     // Receive at most 16 messages.
+    ///     match subscriber.try_recv_many(16) {
+    ///         RecvResult::Ok(msgs) => {
+    ///             for msg in msgs {
+    ///                 pr_info!(logger, "msg = {}", msg.data);
+    ///             }
+    ///         }
+    ///         RecvResult::RetryLater(_) => pr_info!(logger, "retry later"),
+    ///         RecvResult::Err(e) => pr_error!(logger, "error = {}", e),
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid or `max` is 0, or
+    /// - `RCLError::SubscriptionInvalid` if the subscription is invalid, or
+    /// - `RCLError::BadAlloc if allocating` memory failed, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    #[must_use]
+    pub fn try_recv_many(&self, max: usize) -> RecvResult<Vec<TakenMsg<T>>, ()> {
+        #[cfg(feature = "rcl_stat")]
+        let start = std::time::SystemTime::now();
+
+        match take_many::<T>(&self.subscription, max) {
+            Ok(msgs) => {
+                #[cfg(feature = "rcl_stat")]
+                self.subscription.measure_latency(start);
+
+                RecvResult::Ok(msgs)
+            }
+            Err(RCLError::SubscriptionTakeFailed) => {
+                #[cfg(feature = "rcl_stat")]
+                self.subscription.measure_latency(start);
+
+                RecvResult::RetryLater(())
+            }
+            Err(e) => RecvResult::Err(e.into()),
+        }
+    }
+
     /// Receive a message asynchronously.
     ///
     /// This waits and blocks forever until a message arrives.
@@ -388,6 +497,399 @@ impl<T: TypeSupport> Subscriber<T> {
         .await
     }
 
+    /// Convert the subscriber into a `futures_core::Stream` of received messages.
+    ///
+    /// The stream yields `Ok(msg)` whenever a message arrives,
+    /// and `Err(e)` if receiving failed.
+    /// The stream ends when a signal is received or the context is shut down.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures::StreamExt;
+    /// use safe_drive::{
+    ///     logger::Logger, msg::common_interfaces::std_msgs, pr_info,
+    ///     topic::subscriber::Subscriber,
+    /// };
+    ///
+    /// async fn run_subscriber(s: Subscriber<std_msgs::msg::String>) {
+    ///     let logger = Logger::new("subscriber_rs_stream");
+    ///
+    ///     // receive 3 messages
+    ///     let mut stream = s.into_stream().take(3);
+    ///     while let Some(Ok(msg)) = stream.next().await {
+    ///         pr_info!(logger, "Received (stream): msg = {}", msg.data);
+    ///     }
+    /// }
+    /// ```
+    pub fn into_stream(self) -> SubscriberStream<T> {
+        SubscriberStream {
+            subscriber: self,
+            is_waiting: false,
+            is_terminated: false,
+        }
+    }
+
+    /// Non-blocking receive with information of the message.
+    ///
+    /// This is the same as `try_recv` except that `MessageInfo`,
+    /// which includes timestamps and the GID of the publisher, is returned together.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{
+    ///     logger::Logger, msg::common_interfaces::std_msgs, pr_error, pr_info,
+    ///     topic::subscriber::Subscriber, RecvResult,
+    /// };
+    ///
+    /// fn latency(subscriber: Subscriber<std_msgs::msg::UInt32>, logger: Logger) {
+    ///     match subscriber.try_recv_with_info() {
+    ///         RecvResult::Ok((msg, info)) => {
+    ///             let latency = info.received_timestamp - info.source_timestamp;
+    ///             pr_info!(logger, "msg = {}, latency = {}[ns]", msg.data, latency)
+    ///         }
+    ///         RecvResult::RetryLater(_) => pr_info!(logger, "retry later"),
+    ///         RecvResult::Err(e) => pr_error!(logger, "error = {}", e),
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::SubscriptionInvalid` if the subscription is invalid, or
+    /// - `RCLError::BadAlloc` if allocating memory failed, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    #[must_use]
+    pub fn try_recv_with_info(&self) -> RecvResult<(TakenMsg<T>, MessageInfo), ()> {
+        #[cfg(feature = "rcl_stat")]
+        let start = std::time::SystemTime::now();
+
+        match take_with_info::<T>(&self.subscription) {
+            Ok(n) => {
+                #[cfg(feature = "rcl_stat")]
+                self.subscription.measure_latency(start);
+
+                RecvResult::Ok(n)
+            }
+            Err(RCLError::SubscriptionTakeFailed) => {
+                #[cfg(feature = "rcl_stat")]
+                self.subscription.measure_latency(start);
+
+                RecvResult::RetryLater(())
+            }
+            Err(e) => RecvResult::Err(e.into()),
+        }
+    }
+
+    /// Receive a message with information of the message asynchronously.
+    ///
+    /// This is the same as `recv` except that `MessageInfo` is returned together.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::SubscriptionInvalid` if the subscription is invalid, or
+    /// - `RCLError::BadAlloc` if allocating memory failed, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub async fn recv_with_info(&mut self) -> Result<(TakenMsg<T>, MessageInfo), DynError> {
+        AsyncTake {
+            subscription: &mut self.subscription,
+            is_waiting: false,
+            take: take_with_info::<T>,
+        }
+        .await
+    }
+}
+
+impl<T> Subscriber<T> {
+    pub(crate) fn new_with_type_support(
+        node: Arc<Node>,
+        topic_name: &str,
+        type_support: *const rcl::rosidl_message_type_support_t,
+        qos: Option<qos::Profile>,
+
+        #[cfg(all(not(feature = "humble"), not(feature = "galactic")))]
+        disable_loaned_massage: bool,
+    ) -> RCLResult<Self> {
+        #[cfg(any(feature = "humble", feature = "galactic"))]
+        let options = Options::new(&qos.unwrap_or_default());
+
+        #[cfg(all(not(feature = "humble"), not(feature = "galactic")))]
+        let options = Options::new(&qos.unwrap_or_default(), disable_loaned_massage);
+
+        Self::new_with_options(node, topic_name, type_support, &options, None)
+    }
+
+    fn new_with_options(
+        node: Arc<Node>,
+        topic_name: &str,
+        type_support: *const rcl::rosidl_message_type_support_t,
+        options: &Options,
+        intra_process: Option<Arc<IntraProcessQueue>>,
+    ) -> RCLResult<Self> {
+        let mut subscription = Box::new(rcl::MTSafeFn::rcl_get_zero_initialized_subscription());
+
+        let topic_name_c = CString::new(topic_name).unwrap_or_default();
+
+        {
+            let guard = rcl::MT_UNSAFE_FN.lock();
+
+            guard.rcl_subscription_init(
+                subscription.as_mut(),
+                node.as_ptr(),
+                type_support,
+                topic_name_c.as_ptr(),
+                options.as_ptr(),
+            )?;
+        }
+
+        Ok(Subscriber {
+            subscription: Arc::new(RCLSubscription {
+                subscription,
+                node,
+                topic_name: topic_name.to_string(),
+                intra_process,
+
+                #[cfg(feature = "rcl_stat")]
+                latency_take: Mutex::new(TimeStatistics::new()),
+            }),
+            _phantom: Default::default(),
+            _unsync: Default::default(),
+        })
+    }
+
+    pub fn get_topic_name(&self) -> &str {
+        &self.subscription.topic_name
+    }
+
+    /// Get the number of publishers matched to the subscriber.
+    pub fn get_publisher_count(&self) -> RCLResult<usize> {
+        let mut count = 0;
+        rcl::MTSafeFn::rcl_subscription_get_publisher_count(
+            self.subscription.subscription.as_ref(),
+            &mut count,
+        )?;
+
+        #[allow(clippy::unnecessary_cast)] // size_t is not usize on galactic
+        Ok(count as usize)
+    }
+
+    /// Get the QoS profile actually used by the middleware.
+    ///
+    /// `SystemDefault` values of the profile specified when creating the subscriber
+    /// are replaced by the values chosen by the middleware.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::SubscriptionInvalid` if the subscription is invalid.
+    pub fn get_actual_qos(&self) -> RCLResult<qos::Profile> {
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        qos::Profile::from_actual_qos(
+            guard.rcl_subscription_get_actual_qos(self.subscription.subscription.as_ref()),
+            RCLError::SubscriptionInvalid,
+        )
+    }
+
+    /// Create a QoS event of the subscriber.
+    /// See `topic::event`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{
+    ///     msg::common_interfaces::std_msgs,
+    ///     topic::{event::{Event, SubscriberEventType}, subscriber::Subscriber},
+    /// };
+    ///
+    /// fn create_event(subscriber: &Subscriber<std_msgs::msg::Empty>) -> Event {
+    ///     subscriber
+    ///         .create_event(SubscriberEventType::LivelinessChanged)
+    ///         .unwrap()
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::BadAlloc` if allocating memory failed, or
+    /// - `RCLError::Unsupported` if the event type is not supported by the middleware, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn create_event(&self, event_type: SubscriberEventType) -> RCLResult<Event> {
+        let event = RCLEvent::new_subscription(self.subscription.clone(), event_type)?;
+        Ok(Event::new(event))
+    }
+
+    /// Check whether the subscriber filters messages by the middleware.
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub fn is_content_filter_enabled(&self) -> bool {
+        rcl::MTSafeFn::rcl_subscription_is_cft_enabled(self.subscription.subscription.as_ref())
+    }
+
+    /// Set the content filter of the subscriber.
+    /// `expression` is similar to the WHERE part of an SQL clause,
+    /// and `%n` in `expression` is replaced by `parameters[n]`.
+    /// An empty `expression` clears the filter.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{msg::common_interfaces::std_msgs, topic::subscriber::Subscriber};
+    ///
+    /// #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    /// fn filter(subscriber: &Subscriber<std_msgs::msg::UInt32>) {
+    ///     // Receive only messages whose data is greater than 100.
+    ///     subscriber.set_content_filter("data > %0", &["100"]).unwrap();
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::SubscriptionInvalid` if the subscription is invalid, or
+    /// - `RCLError::BadAlloc` if allocating memory failed, or
+    /// - `RCLError::Unsupported` if the middleware does not support content filtered topics, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub fn set_content_filter(&self, expression: &str, parameters: &[&str]) -> RCLResult<()> {
+        let (expression_c, _parameters_c, mut argv) = filter_to_c(expression, parameters)?;
+        let subscription = self.subscription.subscription.as_ref();
+        let mut options =
+            rcl::MTSafeFn::rcl_get_zero_initialized_subscription_content_filter_options();
+
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        guard.rcl_subscription_content_filter_options_init(
+            subscription,
+            expression_c.as_ptr(),
+            argv.len(),
+            argv.as_mut_ptr(),
+            &mut options,
+        )?;
+
+        let result = guard.rcl_subscription_set_content_filter(subscription, &options);
+        let _ = guard.rcl_subscription_content_filter_options_fini(subscription, &mut options);
+
+        result
+    }
+
+    /// Get the content filter of the subscriber.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::BadAlloc` if allocating memory failed, or
+    /// - `RCLError::Unsupported` if the middleware does not support content filtered topics, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub fn get_content_filter(&self) -> RCLResult<ContentFilter> {
+        let subscription = self.subscription.subscription.as_ref();
+        let mut options =
+            rcl::MTSafeFn::rcl_get_zero_initialized_subscription_content_filter_options();
+
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        guard.rcl_subscription_get_content_filter(subscription, &mut options)?;
+
+        let filter = &options.rmw_subscription_content_filter_options;
+        let expression = if filter.filter_expression.is_null() {
+            String::new()
+        } else {
+            unsafe { CStr::from_ptr(filter.filter_expression) }
+                .to_string_lossy()
+                .into_owned()
+        };
+
+        let params = &filter.expression_parameters;
+        let parameters = if params.data.is_null() {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(params.data, params.size) }
+                .iter()
+                .map(|p| unsafe { CStr::from_ptr(*p) }.to_string_lossy().into_owned())
+                .collect()
+        };
+
+        let _ = guard.rcl_subscription_content_filter_options_fini(subscription, &mut options);
+
+        Ok(ContentFilter {
+            expression,
+            parameters,
+        })
+    }
+
+    /// Non-blocking receive of a serialized message.
+    ///
+    /// This is the same as `try_recv` except that the message is not deserialized.
+    /// Use `topic::serialized::deserialize` to get a typed message.
+    ///
+    /// Messages from intra-process publishers are not received by this function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{
+    ///     logger::Logger, msg::common_interfaces::std_msgs, pr_error, pr_info,
+    ///     topic::subscriber::Subscriber, RecvResult,
+    /// };
+    ///
+    /// fn recv_raw(subscriber: Subscriber<std_msgs::msg::UInt32>, logger: Logger) {
+    ///     // Receive the serialized message.
+    ///     match subscriber.try_recv_serialized() {
+    ///         RecvResult::Ok(msg) => pr_info!(logger, "{} bytes", msg.len()),
+    ///         RecvResult::RetryLater(_) => pr_info!(logger, "retry later"),
+    ///         RecvResult::Err(e) => pr_error!(logger, "error = {}", e),
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::SubscriptionInvalid` if the subscription is invalid, or
+    /// - `RCLError::BadAlloc` if allocating memory failed, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    #[must_use]
+    pub fn try_recv_serialized(&self) -> RecvResult<SerializedMessage, ()> {
+        #[cfg(feature = "rcl_stat")]
+        let start = std::time::SystemTime::now();
+
+        match take_serialized(&self.subscription) {
+            Ok(n) => {
+                #[cfg(feature = "rcl_stat")]
+                self.subscription.measure_latency(start);
+
+                RecvResult::Ok(n)
+            }
+            Err(RCLError::SubscriptionTakeFailed) => {
+                #[cfg(feature = "rcl_stat")]
+                self.subscription.measure_latency(start);
+
+                RecvResult::RetryLater(())
+            }
+            Err(e) => RecvResult::Err(e.into()),
+        }
+    }
+
+    /// Receive a serialized message asynchronously.
+    ///
+    /// This is the same as `recv` except that the message is not deserialized.
+    /// Messages from intra-process publishers are not received by this function.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::SubscriptionInvalid` if the subscription is invalid, or
+    /// - `RCLError::BadAlloc` if allocating memory failed, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub async fn recv_serialized(&mut self) -> Result<SerializedMessage, DynError> {
+        AsyncTake {
+            subscription: &mut self.subscription,
+            is_waiting: false,
+            take: take_serialized,
+        }
+        .await
+    }
+
     /// Get latency statistics information of `Mutex` and `rcl_take()`.
     /// Because `rcl_take()` is MT-UNSAFE, a latency includes not only `rcl_take` but also `Mutex`.
     #[cfg(feature = "rcl_stat")]
@@ -409,69 +911,169 @@ impl<'a, T> Future for AsyncReceiver<'a, T> {
     type Output = Result<TakenMsg<T>, DynError>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
-        if is_halt() {
-            return Poll::Ready(Err(Signaled.into()));
+        let this = self.project();
+        poll_take(this.subscription, this.is_waiting, cx, take::<T>)
+    }
+}
+
+#[pinned_drop]
+impl<'a, T> PinnedDrop for AsyncReceiver<'a, T> {
+    fn drop(self: Pin<&mut Self>) {
+        if self.is_waiting {
+            remove_waiting(self.subscription);
         }
+    }
+}
 
-        let this = self.project();
+/// Stream of messages received by a subscriber.
+/// This is created by `Subscriber::into_stream`.
+pub struct SubscriberStream<T> {
+    subscriber: Subscriber<T>,
+    is_waiting: bool,
+    is_terminated: bool,
+}
 
-        let s = this.subscription.clone();
+// No field is structurally pinned.
+impl<T> Unpin for SubscriberStream<T> {}
 
-        // let is_waiting = this.is_waiting;
-        // let subscription = this.subscription;
-        *this.is_waiting = false;
+impl<T> SubscriberStream<T> {
+    /// Get the subscriber of the stream.
+    pub fn get_ref(&self) -> &Subscriber<T> {
+        &self.subscriber
+    }
+}
 
-        #[cfg(feature = "rcl_stat")]
-        let start = std::time::SystemTime::now();
+impl<T: TypeSupport> Stream for SubscriberStream<T> {
+    type Item = Result<TakenMsg<T>, DynError>;
 
-        // try to take 1 message
-        match take::<T>(&s) {
-            Ok(value) => {
-                #[cfg(feature = "rcl_stat")]
-                subscription.measure_latency(start);
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.is_terminated {
+            return Poll::Ready(None);
+        }
 
-                Poll::Ready(Ok(value))
-            } // got
-            Err(RCLError::SubscriptionTakeFailed) => {
-                #[cfg(feature = "rcl_stat")]
-                subscription.measure_latency(start);
-
-                let mut guard = SELECTOR.lock();
-                let mut waker = Some(cx.waker().clone());
-
-                guard.send_command(
-                    &this.subscription.node.context,
-                    async_selector::Command::Subscription(
-                        this.subscription.clone(),
-                        Box::new(move || {
-                            let w = waker.take();
-                            w.unwrap().wake();
-                            CallbackResult::Ok
-                        }),
-                    ),
-                )?;
-
-                *this.is_waiting = true;
-                Poll::Pending
+        match poll_take(
+            &this.subscriber.subscription,
+            &mut this.is_waiting,
+            cx,
+            take::<T>,
+        ) {
+            Poll::Ready(Err(e)) if e.is::<Signaled>() || e.is::<ContextShutdown>() => {
+                this.is_terminated = true;
+                Poll::Ready(None)
             }
-            Err(e) => Poll::Ready(Err(e.into())), // error
+            Poll::Ready(result) => Poll::Ready(Some(result)),
+            Poll::Pending => Poll::Pending,
         }
     }
 }
 
+impl<T: TypeSupport> FusedStream for SubscriberStream<T> {
+    fn is_terminated(&self) -> bool {
+        self.is_terminated
+    }
+}
+
+impl<T> Drop for SubscriberStream<T> {
+    fn drop(&mut self) {
+        if self.is_waiting {
+            remove_waiting(&self.subscriber.subscription);
+        }
+    }
+}
+
+/// Asynchronous receiver taking a message by `take`.
+#[pin_project(PinnedDrop)]
+struct AsyncTake<'a, F> {
+    subscription: &'a mut Arc<RCLSubscription>,
+    is_waiting: bool,
+    take: F,
+}
+
+impl<'a, U, F> Future for AsyncTake<'a, F>
+where
+    F: FnMut(&Arc<RCLSubscription>) -> RCLResult<U>,
+{
+    type Output = Result<U, DynError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        poll_take(this.subscription, this.is_waiting, cx, this.take)
+    }
+}
+
 #[pinned_drop]
-impl<'a, T> PinnedDrop for AsyncReceiver<'a, T> {
+impl<'a, F> PinnedDrop for AsyncTake<'a, F> {
     fn drop(self: Pin<&mut Self>) {
         if self.is_waiting {
+            remove_waiting(self.subscription);
+        }
+    }
+}
+
+/// Try to take a message by `take`.
+/// If no message is available, register `subscription` to the async selector to be woken up.
+fn poll_take<U>(
+    subscription: &Arc<RCLSubscription>,
+    is_waiting: &mut bool,
+    cx: &mut task::Context<'_>,
+    take: impl FnOnce(&Arc<RCLSubscription>) -> RCLResult<U>,
+) -> Poll<Result<U, DynError>> {
+    if is_halt() {
+        return Poll::Ready(Err(Signaled.into()));
+    }
+
+    if !subscription.node.context.is_valid() {
+        return Poll::Ready(Err(ContextShutdown.into()));
+    }
+
+    *is_waiting = false;
+
+    #[cfg(feature = "rcl_stat")]
+    let start = std::time::SystemTime::now();
+
+    // try to take 1 message
+    match take(subscription) {
+        Ok(value) => {
+            #[cfg(feature = "rcl_stat")]
+            subscription.measure_latency(start);
+
+            Poll::Ready(Ok(value))
+        } // got
+        Err(RCLError::SubscriptionTakeFailed) => {
+            #[cfg(feature = "rcl_stat")]
+            subscription.measure_latency(start);
+
             let mut guard = SELECTOR.lock();
-            let _ = guard.send_command(
-                &self.subscription.node.context,
-                async_selector::Command::RemoveSubscription(self.subscription.clone()),
-            );
+            let mut waker = Some(cx.waker().clone());
+
+            guard.send_command(
+                &subscription.node.context,
+                async_selector::Command::Subscription(
+                    subscription.clone(),
+                    Box::new(move || {
+                        let w = waker.take();
+                        w.unwrap().wake();
+                        CallbackResult::Ok
+                    }),
+                ),
+            )?;
+
+            *is_waiting = true;
+            Poll::Pending
         }
+        Err(e) => Poll::Ready(Err(e.into())), // error
     }
 }
 
+fn remove_waiting(subscription: &Arc<RCLSubscription>) {
+    let mut guard = SELECTOR.lock();
+    let _ = guard.send_command(
+        &subscription.node.context,
+        async_selector::Command::RemoveSubscription(subscription.clone()),
+    );
+}
+
 /// Options for subscribers.
 struct Options {
     options: rcl::rcl_subscription_options_t,
@@ -494,11 +1096,69 @@ impl Options {
         Options { options }
     }
 
+    /// Set the content filter, which is copied to a subscription by `rcl_subscription_init`.
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    fn set_content_filter(&mut self, expression: &str, parameters: &[&str]) -> RCLResult<()> {
+        let (expression_c, _parameters_c, mut argv) = filter_to_c(expression, parameters)?;
+
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        guard.rcl_subscription_options_set_content_filter_options(
+            expression_c.as_ptr(),
+            argv.len(),
+            argv.as_mut_ptr(),
+            &mut self.options,
+        )
+    }
+
     pub(crate) fn as_ptr(&self) -> *const rcl::rcl_subscription_options_t {
         &self.options
     }
 }
 
+#[cfg(not(any(feature = "humble", feature = "galactic")))]
+impl Drop for Options {
+    fn drop(&mut self) {
+        if !self
+            .options
+            .rmw_subscription_options
+            .content_filter_options
+            .is_null()
+        {
+            let guard = rcl::MT_UNSAFE_FN.lock();
+            let _ = guard.rcl_subscription_options_fini(&mut self.options);
+        }
+    }
+}
+
+/// Convert a filter expression and its parameters to C strings.
+/// The pointers in the returned vector are valid while the returned `CString`s live.
+/// This returns `RCLError::InvalidArgument` if any of them contains a nul character.
+#[cfg(not(any(feature = "humble", feature = "galactic")))]
+fn filter_to_c(
+    expression: &str,
+    parameters: &[&str],
+) -> RCLResult<(CString, Vec<CString>, Vec<*const c_char>)> {
+    let expression_c = CString::new(expression).or(Err(RCLError::InvalidArgument))?;
+    let parameters_c = parameters
+        .iter()
+        .map(|p| CString::new(*p).or(Err(RCLError::InvalidArgument)))
+        .collect::<RCLResult<Vec<_>>>()?;
+    let argv = parameters_c.iter().map(|p| p.as_ptr()).collect();
+
+    Ok((expression_c, parameters_c, argv))
+}
+
+/// Content filter of a subscriber.
+#[cfg(not(any(feature = "humble", feature = "galactic")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentFilter {
+    /// Filter expression similar to the WHERE part of an SQL clause, such as `"data > %0"`.
+    pub expression: String,
+
+    /// Parameters of the expression. `%n` in the expression is replaced by `parameters[n]`.
+    pub parameters: Vec<String>,
+}
+
 /// A smart pointer for the message taken from the topic with `rcl_take` or `rcl_take_loaned_message`.
 pub enum TakenMsg<T> {
     Copied(T),
@@ -538,16 +1198,205 @@ impl<T> std::ops::DerefMut for TakenMsg<T> {
 unsafe impl<T> Sync for TakenMsg<T> {}
 unsafe impl<T> Send for TakenMsg<T> {}
 
+/// Information of a received message, such as timestamps and the publisher.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageInfo {
+    /// Time when the message was published in nanoseconds.
+    pub source_timestamp: rcl::rmw_time_point_value_t,
+
+    /// Time when the message was received in nanoseconds.
+    pub received_timestamp: rcl::rmw_time_point_value_t,
+
+    /// Sequence number of the message assigned by the publisher.
+    /// This is 0 if the middleware does not support it.
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub publication_sequence_number: u64,
+
+    /// Sequence number of the message assigned by the subscriber.
+    /// This is 0 if the middleware does not support it.
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    pub reception_sequence_number: u64,
+
+    /// Global unique identifier of the publisher.
+    pub publisher_gid: Vec<u8>,
+
+    /// Whether the message was sent via intra-process communication.
+    pub from_intra_process: bool,
+}
+
+impl From<&rcl::rmw_message_info_t> for MessageInfo {
+    fn from(info: &rcl::rmw_message_info_t) -> Self {
+        MessageInfo {
+            source_timestamp: info.source_timestamp,
+            received_timestamp: info.received_timestamp,
+
+            #[cfg(not(any(feature = "humble", feature = "galactic")))]
+            publication_sequence_number: info.publication_sequence_number,
+
+            #[cfg(not(any(feature = "humble", feature = "galactic")))]
+            reception_sequence_number: info.reception_sequence_number,
+
+            publisher_gid: info.publisher_gid.data.to_vec(),
+            from_intra_process: info.from_intra_process,
+        }
+    }
+}
+
 fn take<T>(subscription: &Arc<RCLSubscription>) -> RCLResult<TakenMsg<T>> {
-    if rcl::MTSafeFn::rcl_subscription_can_loan_messages(subscription.subscription.as_ref()) {
-        take_loaned_message(subscription.clone()).map(TakenMsg::Loaned)
+    take_message(subscription, null_mut())
+}
+
+fn take_with_info<T>(subscription: &Arc<RCLSubscription>) -> RCLResult<(TakenMsg<T>, MessageInfo)> {
+    let mut message_info = rcl::MTSafeFn::rmw_get_zero_initialized_message_info();
+    let msg = take_message(subscription, &mut message_info)?;
+    Ok((msg, MessageInfo::from(&message_info)))
+}
+
+fn take_message<T>(
+    subscription: &Arc<RCLSubscription>,
+    message_info: *mut rcl::rmw_message_info_t,
+) -> RCLResult<TakenMsg<T>> {
+    if let Some(queue) = &subscription.intra_process {
+        if let Some(msg) = queue.pop::<T>() {
+            if !message_info.is_null() {
+                let mut info = rcl::MTSafeFn::rmw_get_zero_initialized_message_info();
+                info.from_intra_process = true;
+                unsafe { *message_info = info };
+            }
+            return Ok(TakenMsg::Copied(msg));
+        }
+    }
+
+    let mut info = rcl::MTSafeFn::rmw_get_zero_initialized_message_info();
+    let message_info = if message_info.is_null() {
+        &mut info
     } else {
-        rcl_take(subscription.subscription.as_ref()).map(TakenMsg::Copied)
+        message_info
+    };
+
+    loop {
+        let msg = if rcl::MTSafeFn::rcl_subscription_can_loan_messages(
+            subscription.subscription.as_ref(),
+        ) {
+            take_loaned_message(subscription.clone(), message_info).map(TakenMsg::Loaned)?
+        } else {
+            rcl_take(subscription.subscription.as_ref(), message_info).map(TakenMsg::Copied)?
+        };
+
+        if !is_handed_over(subscription, unsafe { &*message_info }) {
+            return Ok(msg);
+        }
     }
 }
 
+/// Check whether a message taken through the middleware has been handed over
+/// to the intra-process subscription already,
+/// i.e. it is sent by an intra-process publisher of the same context.
+fn is_handed_over(subscription: &RCLSubscription, message_info: &rcl::rmw_message_info_t) -> bool {
+    subscription.intra_process.is_some()
+        && subscription
+            .node
+            .context
+            .intra_process
+            .is_publisher(&message_info.publisher_gid.data)
+}
+
+fn take_many<T>(subscription: &Arc<RCLSubscription>, max: usize) -> RCLResult<Vec<TakenMsg<T>>> {
+    if max == 0 {
+        return Err(RCLError::InvalidArgument);
+    }
+
+    let mut result = Vec::new();
+
+    if let Some(queue) = &subscription.intra_process {
+        while result.len() < max {
+            let Some(msg) = queue.pop::<T>() else {
+                break;
+            };
+            result.push(TakenMsg::Copied(msg));
+        }
+    }
+
+    // Messages of intra-process subscriptions are taken one by one to filter them by publishers.
+    let count = max - result.len();
+    if count > 0
+        && subscription.intra_process.is_none()
+        && !rcl::MTSafeFn::rcl_subscription_can_loan_messages(subscription.subscription.as_ref())
+    {
+        match rcl_take_sequence::<T>(subscription.subscription.as_ref(), count) {
+            Ok(msgs) => {
+                result.extend(msgs.into_iter().map(TakenMsg::Copied));
+                return Ok(result);
+            }
+            Err(RCLError::Unsupported) => (), // take one by one
+            Err(e) => {
+                return if result.is_empty() {
+                    Err(e)
+                } else {
+                    Ok(result)
+                };
+            }
+        }
+    }
+
+    while result.len() < max {
+        match take::<T>(subscription) {
+            Ok(msg) => result.push(msg),
+            Err(e) => {
+                if result.is_empty() {
+                    return Err(e);
+                }
+                break;
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+fn rcl_take_sequence<T>(subscription: &rcl::rcl_subscription_t, count: usize) -> RCLResult<Vec<T>> {
+    let mut messages: Vec<T> = (0..count)
+        .map(|_| unsafe { MaybeUninit::zeroed().assume_init() })
+        .collect();
+    let mut message_ptrs: Vec<*mut c_void> = messages
+        .iter_mut()
+        .map(|msg| msg as *mut T as *mut c_void)
+        .collect();
+    let mut infos: Vec<rcl::rmw_message_info_t> = (0..count)
+        .map(|_| rcl::MTSafeFn::rmw_get_zero_initialized_message_info())
+        .collect();
+
+    // The sequences borrow the vectors, so they must not be finalized.
+    let mut message_sequence = rcl::MTSafeFn::rmw_get_zero_initialized_message_sequence();
+    message_sequence.data = message_ptrs.as_mut_ptr();
+
+    let mut info_sequence = rcl::MTSafeFn::rmw_get_zero_initialized_message_info_sequence();
+    info_sequence.data = infos.as_mut_ptr();
+
+    #[allow(clippy::unnecessary_cast)] // size_t is not usize on galactic
+    {
+        message_sequence.capacity = count as rcl::size_t;
+        info_sequence.capacity = count as rcl::size_t;
+
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        guard.rcl_take_sequence(
+            subscription,
+            count as rcl::size_t,
+            &mut message_sequence,
+            &mut info_sequence,
+            null_mut(),
+        )?;
+    }
+
+    #[allow(clippy::unnecessary_cast)] // size_t is not usize on galactic
+    messages.truncate(message_sequence.size as usize);
+
+    Ok(messages)
+}
+
 fn take_loaned_message<T>(
     subscription: Arc<RCLSubscription>,
+    message_info: *mut rcl::rmw_message_info_t,
 ) -> RCLResult<SubscriberLoanedMessage<T>> {
     let guard = rcl::MT_UNSAFE_FN.lock();
     let message: *mut T = null_mut();
@@ -555,22 +1404,46 @@ fn take_loaned_message<T>(
         .rcl_take_loaned_message(
             subscription.subscription.as_ref(),
             &message as *const _ as *mut _,
-            null_mut(),
+            message_info,
             null_mut(),
         )
         .map(|_| SubscriberLoanedMessage::new(subscription, message))
 }
 
-fn rcl_take<T>(subscription: &rcl::rcl_subscription_t) -> RCLResult<T> {
+fn rcl_take<T>(
+    subscription: &rcl::rcl_subscription_t,
+    message_info: *mut rcl::rmw_message_info_t,
+) -> RCLResult<T> {
     let guard = rcl::MT_UNSAFE_FN.lock();
     let mut ros_message: T = unsafe { MaybeUninit::zeroed().assume_init() };
     match guard.rcl_take(
         subscription,
         &mut ros_message as *mut _ as *mut c_void,
-        null_mut(),
+        message_info,
         null_mut(),
     ) {
         Ok(_) => Ok(ros_message),
         Err(e) => Err(e),
     }
 }
+
+fn take_serialized(subscription: &Arc<RCLSubscription>) -> RCLResult<SerializedMessage> {
+    let mut message_info = rcl::MTSafeFn::rmw_get_zero_initialized_message_info();
+
+    loop {
+        let mut msg = SerializedMessage::new()?;
+        {
+            let guard = rcl::MT_UNSAFE_FN.lock();
+            guard.rcl_take_serialized_message(
+                subscription.subscription.as_ref(),
+                msg.as_ptr_mut(),
+                &mut message_info,
+                null_mut(),
+            )?;
+        }
+
+        if !is_handed_over(subscription, &message_info) {
+            return Ok(msg);
+        }
+    }
+}
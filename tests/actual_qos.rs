@@ -0,0 +1,70 @@
+pub mod common;
+
+use common::msgs::example_msg::msg::Num;
+use safe_drive::{
+    self,
+    context::Context,
+    qos::{
+        policy::{DurabilityPolicy, HistoryPolicy, ReliabilityPolicy},
+        Profile,
+    },
+};
+use std::error::Error;
+
+const TOPIC_NAME: &str = "test_actual_qos";
+
+#[test]
+fn test_actual_qos() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_actual_qos_node", None, Default::default())?;
+
+    let mut qos = Profile::default();
+    qos.depth = 7;
+    qos.reliability = ReliabilityPolicy::BestEffort;
+    qos.durability = DurabilityPolicy::SystemDefault;
+
+    let publisher = node.create_publisher::<Num>(
+        TOPIC_NAME,
+        Some(qos.clone()),
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        true,
+    )?;
+    let subscriber = node.create_subscriber::<Num>(
+        TOPIC_NAME,
+        Some(qos),
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        true,
+    )?;
+
+    for actual in [publisher.get_actual_qos()?, subscriber.get_actual_qos()?] {
+        assert_eq!(actual.history, HistoryPolicy::KeepLast);
+        assert_eq!(actual.depth, 7);
+        assert_eq!(actual.reliability, ReliabilityPolicy::BestEffort);
+
+        // the middleware chooses the durability
+        assert_ne!(actual.durability, DurabilityPolicy::SystemDefault);
+    }
+
+    Ok(())
+}
+
+#[cfg(any(feature = "humble", feature = "iron"))]
+#[test]
+fn test_actual_qos_service() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_actual_qos_service_node", None, Default::default())?;
+
+    let server = common::create_server(node.clone(), "test_actual_qos_service")?;
+    let client = common::create_client(node, "test_actual_qos_service")?;
+
+    for actual in [
+        server.get_request_subscription_actual_qos()?,
+        server.get_response_publisher_actual_qos()?,
+        client.get_request_publisher_actual_qos()?,
+        client.get_response_subscription_actual_qos()?,
+    ] {
+        assert_eq!(actual.reliability, ReliabilityPolicy::Reliable);
+    }
+
+    Ok(())
+}
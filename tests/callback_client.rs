@@ -0,0 +1,106 @@
+pub mod common;
+
+use common::msgs::example_msg::srv::{AddThreeIntsRequest, AddThreeIntsResponse};
+use safe_drive::{self, context::Context};
+use std::{cell::RefCell, error::Error, rc::Rc, time::Duration};
+
+const SERVICE_NAME: &str = "test_callback_client";
+const SERVICE_NAME_TIMEOUT: &str = "test_callback_client_timeout";
+
+#[test]
+fn test_callback_client() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_server = ctx.create_node("test_callback_server_node", None, Default::default())?;
+    let node_client = ctx.create_node("test_callback_client_node", None, Default::default())?;
+
+    let server = common::create_server(node_server, SERVICE_NAME)?;
+    let client = common::create_client(node_client, SERVICE_NAME)?;
+    assert!(client.wait_for_service(Duration::from_secs(3))?);
+
+    let mut selector = ctx.create_selector()?;
+    selector.add_server(
+        server,
+        Box::new(|request, _header| AddThreeIntsResponse {
+            sum: request.a + request.b + request.c,
+        }),
+    );
+
+    let client = selector.add_client(client).unwrap();
+
+    let received: Rc<RefCell<Vec<i64>>> = Default::default();
+    let received_cloned = received.clone();
+
+    // send requests in a callback function
+    selector.add_timer(
+        Duration::from_millis(10),
+        Box::new(move || {
+            for n in 0..3 {
+                let received = received_cloned.clone();
+                client
+                    .call_with_callback(
+                        &AddThreeIntsRequest { a: n, b: 0, c: 0 },
+                        Some(Duration::from_secs(3)),
+                        Box::new(move |response| {
+                            let (response, _header) = response.expect("timeout");
+                            received.borrow_mut().push(response.sum);
+                        }),
+                    )
+                    .unwrap();
+            }
+        }),
+    );
+
+    for _ in 0..20 {
+        if received.borrow().len() == 3 {
+            break;
+        }
+        selector.wait_timeout(Duration::from_millis(100))?;
+    }
+
+    let mut received = received.borrow().clone();
+    received.sort();
+    assert_eq!(received, vec![0, 1, 2]);
+
+    Ok(())
+}
+
+#[test]
+fn test_callback_client_timeout() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node(
+        "test_callback_client_timeout_node",
+        None,
+        Default::default(),
+    )?;
+
+    // nobody responds
+    let client = common::create_client(node, SERVICE_NAME_TIMEOUT)?;
+
+    let mut selector = ctx.create_selector()?;
+    let client = selector.add_client(client).unwrap();
+
+    let timed_out = Rc::new(RefCell::new(false));
+    let timed_out_cloned = timed_out.clone();
+
+    client.call_with_callback(
+        &AddThreeIntsRequest { a: 1, b: 2, c: 3 },
+        Some(Duration::from_millis(50)),
+        Box::new(move |response| {
+            assert!(response.is_none());
+            *timed_out_cloned.borrow_mut() = true;
+        }),
+    )?;
+    assert_eq!(client.num_pending(), 1);
+
+    for _ in 0..10 {
+        if *timed_out.borrow() {
+            break;
+        }
+        selector.wait_timeout(Duration::from_millis(100))?;
+    }
+
+    assert!(*timed_out.borrow());
+    assert_eq!(client.num_pending(), 0);
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+pub mod common;
+
+use common::msgs::example_msg::srv::{AddThreeInts, AddThreeIntsRequest, AddThreeIntsResponse};
+use safe_drive::{self, context::Context, error::DynError, service::server::ConcurrentServer};
+use std::{error::Error, thread, time::Duration};
+
+const SERVICE_NAME: &str = "test_concurrent_server";
+
+#[test]
+fn test_concurrent_server() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_server = ctx.create_node("test_concurrent_server_node", None, Default::default())?;
+    let node_client = ctx.create_node("test_concurrent_client_node", None, Default::default())?;
+
+    let server = common::create_server(node_server, SERVICE_NAME)?.into_concurrent();
+    let client = common::create_client(node_client, SERVICE_NAME)?;
+    assert!(client.wait_for_service(Duration::from_secs(3))?);
+
+    let client = client.into_pipelined();
+
+    async_std::task::block_on(async {
+        let s = async_std::task::spawn(async {
+            async_std::future::timeout(Duration::from_secs(3), run_server(server))
+                .await
+                .expect("timeout")
+                .unwrap();
+        });
+
+        let mut receivers = Vec::new();
+        for n in 0..3 {
+            let request = AddThreeIntsRequest { a: n, b: n, c: n };
+            receivers.push((n, client.send(&request).unwrap()));
+        }
+
+        for (n, receiver) in receivers {
+            let (response, _header) = async_std::future::timeout(Duration::from_secs(3), receiver)
+                .await
+                .expect("timeout")
+                .unwrap();
+            assert_eq!(response.sum, n * 3);
+        }
+
+        s.await;
+    });
+
+    Ok(())
+}
+
+/// Receive all the requests first, and then respond in the reverse order from other threads.
+async fn run_server(mut server: ConcurrentServer<AddThreeInts>) -> Result<(), DynError> {
+    let mut requests = Vec::new();
+    for _ in 0..3 {
+        requests.push(server.recv().await?);
+    }
+
+    let handles: Vec<_> = requests
+        .into_iter()
+        .rev()
+        .map(|(responder, request, _header)| {
+            thread::spawn(move || {
+                let response = AddThreeIntsResponse {
+                    sum: request.a + request.b + request.c,
+                };
+                responder.send(&response).map_err(|(_, e)| e)
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+#![cfg(not(any(feature = "humble", feature = "galactic")))]
+
+pub mod common;
+
+use common::msgs::example_msg::msg::Num;
+use safe_drive::{self, context::Context, error::RCLError, RecvResult};
+use std::{error::Error, thread, time::Duration};
+
+const TOPIC_NAME: &str = "test_content_filter";
+
+#[test]
+fn test_content_filter() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_content_filter_node", None, Default::default())?;
+
+    let subscriber = match node.create_subscriber_with_filter::<Num>(
+        TOPIC_NAME,
+        None,
+        "num > %0",
+        &["10"],
+        true,
+    ) {
+        Ok(subscriber) => subscriber,
+        Err(RCLError::Unsupported) => {
+            // the middleware cannot filter messages
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let filter = subscriber.get_content_filter()?;
+    assert_eq!(filter.expression, "num > %0");
+    assert_eq!(filter.parameters, vec!["10".to_string()]);
+
+    let publisher = common::create_publisher(node, TOPIC_NAME, true)?;
+    publisher.wait_for_subscribers(1, Duration::from_secs(3))?;
+
+    // only 20 passes the filter
+    for n in [5, 20] {
+        let mut msg = Num::new().unwrap();
+        msg.num = n;
+        publisher.send(&msg)?;
+    }
+
+    thread::sleep(Duration::from_millis(100));
+    match subscriber.try_recv() {
+        RecvResult::Ok(msg) => assert_eq!(msg.num, 20),
+        _ => panic!("failed to receive a message"),
+    }
+    assert!(matches!(subscriber.try_recv(), RecvResult::RetryLater(_)));
+
+    // a nul character is not allowed
+    assert!(matches!(
+        subscriber.set_content_filter("num > %0\0", &["10"]),
+        Err(RCLError::InvalidArgument)
+    ));
+    assert!(matches!(
+        subscriber.set_content_filter("num > %0", &["1\00"]),
+        Err(RCLError::InvalidArgument)
+    ));
+
+    // clear the filter
+    subscriber.set_content_filter("", &[])?;
+
+    Ok(())
+}
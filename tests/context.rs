@@ -0,0 +1,58 @@
+pub mod common;
+
+use common::msgs::example_msg::msg::Num;
+use safe_drive::{self, context::ContextBuilder, RecvResult};
+use std::{error::Error, thread, time::Duration};
+
+const TOPIC_NAME: &str = "test_context_builder";
+
+#[test]
+fn test_context_builder() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    // create independent contexts on different domains
+    let ctx1 = ContextBuilder::new()
+        .args(["test_context_builder"])
+        .domain_id(41)
+        .build()?;
+    let ctx2 = ContextBuilder::new()
+        .args(["test_context_builder"])
+        .domain_id(42)
+        .build()?;
+
+    assert_ne!(ctx1.as_ref() as *const _, ctx2.as_ref() as *const _);
+    assert_eq!(ctx1.get_domain_id()?, 41);
+    assert_eq!(ctx2.get_domain_id()?, 42);
+
+    // create nodes
+    let node_pub = ctx1.create_node("test_context_builder_pub_node", None, Default::default())?;
+    let node_sub1 = ctx1.create_node("test_context_builder_sub_node", None, Default::default())?;
+    let node_sub2 = ctx2.create_node("test_context_builder_sub_node", None, Default::default())?;
+
+    // create a publisher and subscribers
+    let publisher = common::create_publisher(node_pub, TOPIC_NAME, true)?;
+    let subscriber1 = common::create_subscriber(node_sub1, TOPIC_NAME, true)?;
+    let subscriber2 = common::create_subscriber(node_sub2, TOPIC_NAME, true)?;
+
+    // create selectors
+    let mut selector1 = ctx1.create_selector()?;
+    let mut selector2 = ctx2.create_selector()?;
+
+    thread::sleep(Duration::from_millis(100));
+
+    // publish a message
+    let n = 100;
+    publisher.send(&Num { num: n })?;
+
+    // only the subscriber on the same domain receives the message
+    assert!(selector1.add_subscriber(
+        subscriber1,
+        Box::new(move |msg| {
+            assert_eq!(msg.num, n);
+        }),
+    ));
+    assert!(selector1.wait_timeout(Duration::from_millis(500))?);
+
+    assert!(!selector2.wait_timeout(Duration::from_millis(100))?);
+    assert!(matches!(subscriber2.try_recv(), RecvResult::RetryLater(_)));
+
+    Ok(())
+}
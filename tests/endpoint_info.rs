@@ -0,0 +1,46 @@
+pub mod common;
+
+use safe_drive::{self, context::Context, node::graph::EndpointType, qos};
+use std::{error::Error, thread, time::Duration};
+
+const TOPIC_NAME: &str = "test_endpoint_info";
+
+#[test]
+fn test_endpoint_info() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_pub = ctx.create_node("test_endpoint_info_pub_node", None, Default::default())?;
+    let node_sub = ctx.create_node("test_endpoint_info_sub_node", None, Default::default())?;
+
+    let publisher = common::create_publisher(node_pub.clone(), TOPIC_NAME, true)?;
+    let subscriber = common::create_subscriber(node_sub, TOPIC_NAME, true)?;
+
+    thread::sleep(Duration::from_millis(100));
+
+    // matched counts
+    assert_eq!(publisher.get_subscription_count()?, 1);
+    assert_eq!(subscriber.get_publisher_count()?, 1);
+
+    assert_eq!(node_pub.count_publishers(TOPIC_NAME)?, 1);
+    assert_eq!(node_pub.count_subscribers(TOPIC_NAME)?, 1);
+
+    // endpoint information
+    let publishers = node_pub.get_publishers_info_by_topic(TOPIC_NAME)?;
+    assert_eq!(publishers.len(), 1);
+
+    let info = &publishers[0];
+    assert_eq!(info.node_name, "test_endpoint_info_pub_node");
+    assert_eq!(info.node_namespace, "/");
+    assert_eq!(info.topic_type, "example_msg/msg/Num");
+    assert_eq!(info.endpoint_type, EndpointType::Publisher);
+    assert_eq!(
+        info.qos.reliability,
+        qos::policy::ReliabilityPolicy::Reliable
+    );
+
+    let subscriptions = node_pub.get_subscriptions_info_by_topic(TOPIC_NAME)?;
+    assert_eq!(subscriptions.len(), 1);
+    assert_eq!(subscriptions[0].endpoint_type, EndpointType::Subscription);
+    assert_ne!(subscriptions[0].gid, info.gid);
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use safe_drive::{
+    self,
+    context::Context,
+    msg::common_interfaces::std_msgs,
+    topic::{
+        generic::TypeSupportError,
+        serialized::{deserialize, serialize},
+    },
+    RecvResult,
+};
+use std::{error::Error, thread, time::Duration};
+
+const TOPIC_NAME: &str = "test_generic";
+const TYPE_NAME: &str = "std_msgs/msg/UInt32";
+
+#[test]
+fn test_generic_pubsub() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_pub = ctx.create_node("test_generic_pub_node", None, Default::default())?;
+    let node_sub = ctx.create_node("test_generic_sub_node", None, Default::default())?;
+
+    #[cfg(any(feature = "humble", feature = "galactic"))]
+    let (publisher, subscriber) = (
+        node_pub.create_generic_publisher(TOPIC_NAME, TYPE_NAME, None)?,
+        node_sub.create_generic_subscriber(TOPIC_NAME, TYPE_NAME, None)?,
+    );
+
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    let (publisher, subscriber) = (
+        node_pub.create_generic_publisher(TOPIC_NAME, TYPE_NAME, None, true)?,
+        node_sub.create_generic_subscriber(TOPIC_NAME, TYPE_NAME, None, true)?,
+    );
+
+    assert_eq!(publisher.get_type_name(), TYPE_NAME);
+    assert_eq!(subscriber.get_topic_name(), TOPIC_NAME);
+
+    // the topic type is visible in the ROS graph
+    let topics = node_pub.get_topic_names_and_types()?;
+    assert_eq!(
+        topics[&format!("/{TOPIC_NAME}")],
+        vec![TYPE_NAME.to_string()]
+    );
+
+    for _ in 0..30 {
+        if publisher.get_subscription_count()? > 0 {
+            break;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    let mut msg = std_msgs::msg::UInt32::new().unwrap();
+    msg.data = 1234;
+    publisher.send(&serialize(&msg)?)?;
+
+    let mut received = None;
+    for _ in 0..10 {
+        match subscriber.try_recv() {
+            RecvResult::Ok(msg) => {
+                received = Some(msg);
+                break;
+            }
+            RecvResult::RetryLater(_) => thread::sleep(Duration::from_millis(100)),
+            RecvResult::Err(e) => return Err(e),
+        }
+    }
+
+    let msg: std_msgs::msg::UInt32 = deserialize(&received.expect("timeout"))?;
+    assert_eq!(msg.data, 1234);
+
+    Ok(())
+}
+
+#[test]
+fn test_generic_invalid_type() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_generic_invalid_node", None, Default::default())?;
+
+    #[cfg(any(feature = "humble", feature = "galactic"))]
+    let result = node.create_generic_publisher(TOPIC_NAME, "std_msgs/msg/", None);
+
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    let result = node.create_generic_publisher(TOPIC_NAME, "std_msgs/msg/", None, true);
+
+    let err = result.err().unwrap();
+    assert!(matches!(
+        err.downcast_ref::<TypeSupportError>(),
+        Some(TypeSupportError::InvalidTypeName(_))
+    ));
+
+    #[cfg(any(feature = "humble", feature = "galactic"))]
+    let result = node.create_generic_subscriber(TOPIC_NAME, "no_such_msgs/msg/Foo", None);
+
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    let result = node.create_generic_subscriber(TOPIC_NAME, "no_such_msgs/msg/Foo", None, true);
+
+    let err = result.err().unwrap();
+    assert!(matches!(
+        err.downcast_ref::<TypeSupportError>(),
+        Some(TypeSupportError::LibraryNotFound { .. })
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+pub mod common;
+
+use safe_drive::{self, context::Context};
+use std::{error::Error, thread, time::Duration};
+
+const TOPIC_NAME: &str = "test_graph_topic";
+const SERVICE_NAME: &str = "test_graph_service";
+const NODE_NAME: &str = "test_graph_node";
+
+#[test]
+fn test_graph() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node(NODE_NAME, None, Default::default())?;
+
+    let _publisher = common::create_publisher(node.clone(), TOPIC_NAME, true)?;
+    let _server = common::create_server(node.clone(), SERVICE_NAME)?;
+
+    thread::sleep(Duration::from_millis(100));
+
+    // topics and services
+    let topics = node.get_topic_names_and_types()?;
+    let types = &topics[&format!("/{TOPIC_NAME}")];
+    assert_eq!(types, &["example_msg/msg/Num".to_string()]);
+
+    let services = node.get_service_names_and_types()?;
+    let types = &services[&format!("/{SERVICE_NAME}")];
+    assert_eq!(types, &["example_msg/srv/AddThreeInts".to_string()]);
+
+    // nodes
+    let nodes = node.get_node_names_and_namespaces()?;
+    assert!(nodes.contains(&(NODE_NAME.to_string(), "/".to_string())));
+
+    // per node
+    let topics = node.get_publisher_names_and_types_by_node(NODE_NAME, "/")?;
+    assert!(topics.contains_key(&format!("/{TOPIC_NAME}")));
+
+    let topics = node.get_subscriber_names_and_types_by_node(NODE_NAME, "/")?;
+    assert!(!topics.contains_key(&format!("/{TOPIC_NAME}")));
+
+    let services = node.get_service_names_and_types_by_node(NODE_NAME, "/")?;
+    assert!(services.contains_key(&format!("/{SERVICE_NAME}")));
+
+    assert!(node
+        .get_publisher_names_and_types_by_node("test_graph_no_such_node", "/")
+        .is_err());
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+pub mod common;
+
+use safe_drive::{
+    self,
+    context::{Context, ContextBuilder},
+};
+use std::{
+    error::Error,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+const TOPIC_NAME: &str = "test_graph_event";
+const TOPIC_NAME_ASYNC: &str = "test_graph_event_async";
+
+#[test]
+fn test_graph_event_handler() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_watch = ctx.create_node("test_graph_event_watch_node", None, Default::default())?;
+    let node_pub = ctx.create_node("test_graph_event_pub_node", None, Default::default())?;
+
+    let is_changed = Arc::new(AtomicBool::new(false));
+    let is_changed_cloned = is_changed.clone();
+
+    let mut selector = ctx.create_selector()?;
+    assert!(selector.add_graph_event_handler(
+        &node_watch,
+        Box::new(move || is_changed_cloned.store(true, Ordering::Relaxed)),
+    ));
+
+    let _publisher = common::create_publisher(node_pub, TOPIC_NAME, true)?;
+
+    for _ in 0..10 {
+        selector.wait_timeout(Duration::from_millis(100))?;
+        if is_changed.load(Ordering::Relaxed) {
+            break;
+        }
+    }
+    assert!(is_changed.load(Ordering::Relaxed));
+
+    // a node of another context cannot be registered
+    let ctx2 = ContextBuilder::new().args(["test_graph_event"]).build()?;
+    let node2 = ctx2.create_node("test_graph_event_node2", None, Default::default())?;
+    assert!(!selector.add_graph_event_handler(&node2, Box::new(|| ())));
+
+    selector.remove_graph_event_handler(&node_watch);
+
+    Ok(())
+}
+
+#[test]
+fn test_graph_changed_async() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_watch = ctx.create_node(
+        "test_graph_changed_async_watch_node",
+        None,
+        Default::default(),
+    )?;
+    let node_pub = ctx.create_node(
+        "test_graph_changed_async_pub_node",
+        None,
+        Default::default(),
+    )?;
+
+    let th = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        common::create_publisher(node_pub, TOPIC_NAME_ASYNC, true).unwrap()
+    });
+
+    async_std::task::block_on(async {
+        async_std::future::timeout(Duration::from_secs(3), node_watch.graph_changed())
+            .await
+            .expect("timeout")
+    })?;
+
+    let _publisher = th.join().unwrap();
+
+    Ok(())
+}
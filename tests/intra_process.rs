@@ -0,0 +1,189 @@
+pub mod common;
+
+use common::msgs::example_msg::msg::Num;
+use safe_drive::{
+    self,
+    context::Context,
+    node::Node,
+    topic::{publisher::Publisher, subscriber::Subscriber},
+    RecvResult,
+};
+use std::{
+    error::Error,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+const TOPIC_NAME: &str = "test_intra_process";
+const TOPIC_NAME_FAN_OUT: &str = "test_intra_process_fan_out";
+const TOPIC_NAME_ASYNC: &str = "test_intra_process_async";
+const TOPIC_NAME_MIXED: &str = "test_intra_process_mixed";
+
+fn create_publisher(
+    node: &Arc<Node>,
+    topic_name: &str,
+) -> Result<Publisher<Num>, Box<dyn Error + Sync + Send + 'static>> {
+    let publisher = node.create_intra_process_publisher(
+        topic_name,
+        None,
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        true,
+    )?;
+    Ok(publisher)
+}
+
+fn create_subscriber(
+    node: &Arc<Node>,
+    topic_name: &str,
+) -> Result<Subscriber<Num>, Box<dyn Error + Sync + Send + 'static>> {
+    let subscriber = node.create_intra_process_subscriber(
+        topic_name,
+        None,
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        true,
+    )?;
+    Ok(subscriber)
+}
+
+#[test]
+fn test_intra_process() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_intra_process_node", None, Default::default())?;
+
+    let subscriber = create_subscriber(&node, TOPIC_NAME)?;
+    let publisher = create_publisher(&node, TOPIC_NAME)?;
+    assert!(publisher.is_intra_process());
+
+    // moved without copying
+    publisher.send_owned(Num { num: 10 })?;
+
+    // copied by serialization
+    publisher.send(&Num { num: 20 })?;
+
+    for n in [10, 20] {
+        match subscriber.try_recv() {
+            RecvResult::Ok(msg) => assert_eq!(msg.num, n),
+            _ => panic!("failed to receive a message"),
+        }
+    }
+    assert!(matches!(subscriber.try_recv(), RecvResult::RetryLater(_)));
+
+    // message info of intra-process messages
+    publisher.send_owned(Num { num: 30 })?;
+    match subscriber.try_recv_with_info() {
+        RecvResult::Ok((msg, info)) => {
+            assert_eq!(msg.num, 30);
+            assert!(info.from_intra_process);
+        }
+        _ => panic!("failed to receive a message"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_intra_process_fan_out() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_intra_process_fan_out_node", None, Default::default())?;
+
+    let subscriber1 = create_subscriber(&node, TOPIC_NAME_FAN_OUT)?;
+    let subscriber2 = create_subscriber(&node, TOPIC_NAME_FAN_OUT)?;
+    let publisher = create_publisher(&node, TOPIC_NAME_FAN_OUT)?;
+
+    publisher.send_owned(Num { num: 100 })?;
+
+    for subscriber in [&subscriber1, &subscriber2] {
+        match subscriber.try_recv() {
+            RecvResult::Ok(msg) => assert_eq!(msg.num, 100),
+            _ => panic!("failed to receive a message"),
+        }
+    }
+
+    // dropped subscribers no longer receive messages
+    drop(subscriber2);
+    publisher.send_owned(Num { num: 200 })?;
+
+    match subscriber1.try_recv() {
+        RecvResult::Ok(msg) => assert_eq!(msg.num, 200),
+        _ => panic!("failed to receive a message"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_intra_process_async() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_intra_process_async_node", None, Default::default())?;
+
+    let mut subscriber = create_subscriber(&node, TOPIC_NAME_ASYNC)?;
+    let publisher = create_publisher(&node, TOPIC_NAME_ASYNC)?;
+
+    async_std::task::block_on(async {
+        let p = async_std::task::spawn(async move {
+            async_std::task::sleep(Duration::from_millis(100)).await;
+            publisher.send_owned(Num { num: 300 }).unwrap();
+        });
+
+        let msg = async_std::future::timeout(Duration::from_secs(3), subscriber.recv())
+            .await
+            .expect("timeout")
+            .unwrap();
+        assert_eq!(msg.num, 300);
+
+        p.await;
+    });
+
+    Ok(())
+}
+
+#[test]
+fn test_intra_process_mixed() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_intra_process_mixed_node", None, Default::default())?;
+
+    let subscriber = create_subscriber(&node, TOPIC_NAME_MIXED)?;
+    let publisher = create_publisher(&node, TOPIC_NAME_MIXED)?;
+
+    // a publisher and a subscriber of the same context which are not intra-process
+    let plain_publisher = common::create_publisher(node.clone(), TOPIC_NAME_MIXED, true)?;
+    let plain_subscriber = common::create_subscriber(node.clone(), TOPIC_NAME_MIXED, true)?;
+
+    assert!(publisher.wait_for_subscribers(2, Duration::from_secs(3))?);
+    assert!(plain_publisher.wait_for_subscribers(2, Duration::from_secs(3))?);
+
+    // handed over in-process, and sent through the middleware for `plain_subscriber`
+    publisher.send(&Num { num: 1 })?;
+
+    // received through the middleware
+    plain_publisher.send(&Num { num: 2 })?;
+
+    let mut received = Vec::new();
+    let deadline = Instant::now() + Duration::from_secs(3);
+    while received.len() < 2 && Instant::now() < deadline {
+        match subscriber.try_recv() {
+            RecvResult::Ok(msg) => received.push(msg.num),
+            RecvResult::RetryLater(_) => std::thread::sleep(Duration::from_millis(10)),
+            RecvResult::Err(e) => return Err(e),
+        }
+    }
+    assert_eq!(received, [1, 2]);
+
+    // the message of the intra-process publisher is not received twice
+    std::thread::sleep(Duration::from_millis(100));
+    assert!(matches!(subscriber.try_recv(), RecvResult::RetryLater(_)));
+
+    let mut received = Vec::new();
+    let deadline = Instant::now() + Duration::from_secs(3);
+    while received.len() < 2 && Instant::now() < deadline {
+        match plain_subscriber.try_recv() {
+            RecvResult::Ok(msg) => received.push(msg.num),
+            RecvResult::RetryLater(_) => std::thread::sleep(Duration::from_millis(10)),
+            RecvResult::Err(e) => return Err(e),
+        }
+    }
+    received.sort();
+    assert_eq!(received, [1, 2]);
+
+    Ok(())
+}
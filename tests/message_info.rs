@@ -0,0 +1,78 @@
+pub mod common;
+
+use common::msgs::example_msg::msg::Num;
+use safe_drive::{self, context::Context, topic::subscriber::MessageInfo};
+use std::{cell::RefCell, error::Error, rc::Rc, time::Duration};
+
+const TOPIC_NAME: &str = "test_message_info";
+const TOPIC_NAME_ASYNC: &str = "test_message_info_async";
+
+#[test]
+fn test_message_info() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_pub = ctx.create_node("test_message_info_pub_node", None, Default::default())?;
+    let node_sub = ctx.create_node("test_message_info_sub_node", None, Default::default())?;
+
+    let publisher = common::create_publisher(node_pub.clone(), TOPIC_NAME, true)?;
+    let subscriber = common::create_subscriber(node_sub, TOPIC_NAME, true)?;
+    assert!(publisher.wait_for_subscribers(1, Duration::from_secs(3))?);
+
+    publisher.send(&Num { num: 100 })?;
+
+    // receive the message with information
+    let received: Rc<RefCell<Option<MessageInfo>>> = Default::default();
+    let received_cloned = received.clone();
+
+    let mut selector = ctx.create_selector()?;
+    selector.add_subscriber_with_info(
+        subscriber,
+        Box::new(move |msg, info| {
+            assert_eq!(msg.num, 100);
+            *received_cloned.borrow_mut() = Some(info);
+        }),
+    );
+    selector.wait()?;
+
+    let info = received.borrow_mut().take().unwrap();
+    assert!(info.source_timestamp > 0);
+    assert!(info.received_timestamp >= info.source_timestamp);
+
+    // GID of the publisher
+    let publishers = node_pub.get_publishers_info_by_topic(TOPIC_NAME)?;
+    assert_eq!(info.publisher_gid, publishers[0].gid);
+
+    Ok(())
+}
+
+#[test]
+fn test_message_info_async() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_pub = ctx.create_node("test_message_info_async_pub_node", None, Default::default())?;
+    let node_sub = ctx.create_node("test_message_info_async_sub_node", None, Default::default())?;
+
+    let publisher = common::create_publisher(node_pub, TOPIC_NAME_ASYNC, true)?;
+    let mut subscriber = common::create_subscriber(node_sub, TOPIC_NAME_ASYNC, true)?;
+    assert!(publisher.wait_for_subscribers(1, Duration::from_secs(3))?);
+
+    publisher.send(&Num { num: 1 })?;
+    publisher.send(&Num { num: 2 })?;
+
+    async_std::task::block_on(async {
+        let (msg1, info1) = subscriber.recv_with_info().await?;
+        let (msg2, info2) = subscriber.recv_with_info().await?;
+        assert_eq!((msg1.num, msg2.num), (1, 2));
+        assert!(info1.source_timestamp <= info2.source_timestamp);
+
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        if info1.publication_sequence_number != 0 {
+            assert_eq!(
+                info1.publication_sequence_number + 1,
+                info2.publication_sequence_number
+            );
+        }
+
+        Ok::<(), Box<dyn Error + Sync + Send + 'static>>(())
+    })?;
+
+    Ok(())
+}
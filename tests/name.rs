@@ -0,0 +1,72 @@
+pub mod common;
+
+use safe_drive::{
+    self,
+    context::Context,
+    error::RCLError,
+    name::{InvalidNameError, InvalidNameReason},
+    node::NodeOptions,
+};
+use std::error::Error;
+
+#[test]
+fn test_resolve_name() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+
+    let options = NodeOptions::builder()
+        .remap("/ns/chatter", "/ns/chatter_remapped")
+        .build()?;
+    let node = ctx.create_node("test_resolve_name_node", Some("/ns"), options)?;
+
+    assert_eq!(
+        node.get_fully_qualified_name(),
+        "/ns/test_resolve_name_node"
+    );
+
+    // relative, private, and absolute names
+    assert_eq!(node.resolve_topic_name("scan")?, "/ns/scan");
+    assert_eq!(
+        node.resolve_topic_name("~/scan")?,
+        "/ns/test_resolve_name_node/scan"
+    );
+    assert_eq!(node.resolve_service_name("/reset")?, "/reset");
+
+    // remapping
+    assert_eq!(node.resolve_topic_name("chatter")?, "/ns/chatter_remapped");
+
+    // invalid name
+    let err = node.resolve_topic_name("ns/chat ter").unwrap_err();
+    let err = err.downcast_ref::<InvalidNameError>().unwrap();
+    assert_eq!(err.index, 7);
+    assert_eq!(err.reason, InvalidNameReason::UnallowedCharacter(' '));
+
+    Ok(())
+}
+
+#[test]
+fn test_create_with_invalid_name() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node(
+        "test_create_with_invalid_name_node",
+        None,
+        Default::default(),
+    )?;
+
+    // creating entities fails without details
+    assert!(matches!(
+        common::create_publisher(node.clone(), "/a/2d-scan", true),
+        Err(RCLError::TopicNameInvalid)
+    ));
+    assert!(matches!(
+        common::create_server(node.clone(), "/a/2d-reset"),
+        Err(RCLError::ServiceNameInvalid)
+    ));
+
+    // resolving the name tells why it is invalid
+    let err = node.resolve_topic_name("/a/2d-scan").unwrap_err();
+    let err = err.downcast_ref::<InvalidNameError>().unwrap();
+    assert_eq!(err.index, 3);
+    assert_eq!(err.reason, InvalidNameReason::TokenStartsWithNumber);
+
+    Ok(())
+}
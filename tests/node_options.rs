@@ -0,0 +1,49 @@
+pub mod common;
+
+use common::msgs::example_msg::msg::Num;
+use safe_drive::{self, context::Context, error::RCLError, node::NodeOptions};
+use std::{error::Error, thread, time::Duration};
+
+const TOPIC_NAME: &str = "test_node_options";
+const TOPIC_NAME_REMAPPED: &str = "test_node_options_remapped";
+
+#[test]
+fn test_node_options_remap() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+
+    // the publisher's topic is remapped
+    let options = NodeOptions::builder()
+        .remap(TOPIC_NAME, TOPIC_NAME_REMAPPED)
+        .enable_rosout(false)
+        .build()?;
+    let node_pub = ctx.create_node("test_node_options_pub_node", None, options)?;
+    let node_sub = ctx.create_node("test_node_options_sub_node", None, Default::default())?;
+
+    let publisher = common::create_publisher(node_pub, TOPIC_NAME, true)?;
+    let subscriber = common::create_subscriber(node_sub, TOPIC_NAME_REMAPPED, true)?;
+
+    let mut selector = ctx.create_selector()?;
+
+    thread::sleep(Duration::from_millis(100));
+
+    let n = 200;
+    publisher.send(&Num { num: n })?;
+
+    selector.add_subscriber(
+        subscriber,
+        Box::new(move |msg| {
+            assert_eq!(msg.num, n);
+        }),
+    );
+    assert!(selector.wait_timeout(Duration::from_millis(500))?);
+
+    Ok(())
+}
+
+#[test]
+fn test_node_options_invalid_args() {
+    let result = NodeOptions::builder()
+        .arguments(["--ros-args", "--unknown-flag"])
+        .build();
+    assert!(matches!(result, Err(RCLError::InvalidRosArgs)));
+}
@@ -0,0 +1,156 @@
+pub mod common;
+
+use common::msgs::example_msg::srv::{AddThreeInts, AddThreeIntsRequest, AddThreeIntsResponse};
+use safe_drive::{self, context::Context, error::DynError, service::server::Server, RecvResult};
+use std::{error::Error, time::Duration};
+
+const SERVICE_NAME: &str = "test_pipelined_client";
+const SERVICE_NAME_TIMEOUT: &str = "test_pipelined_client_timeout";
+const SERVICE_NAME_DRAIN: &str = "test_pipelined_client_drain";
+
+#[test]
+fn test_pipelined_client() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_server = ctx.create_node("test_pipelined_server_node", None, Default::default())?;
+    let node_client = ctx.create_node("test_pipelined_client_node", None, Default::default())?;
+
+    let server = common::create_server(node_server, SERVICE_NAME)?;
+    let client = common::create_client(node_client, SERVICE_NAME)?;
+    assert!(client.wait_for_service(Duration::from_secs(3))?);
+
+    let client = client.into_pipelined();
+
+    async_std::task::block_on(async {
+        let s = async_std::task::spawn(async {
+            let _ = async_std::future::timeout(Duration::from_secs(3), run_server(server, 5)).await;
+        });
+
+        // send all the requests before receiving responses
+        let mut receivers = Vec::new();
+        for n in 0..5 {
+            let request = AddThreeIntsRequest { a: n, b: 0, c: 0 };
+            receivers.push((n, client.send(&request).unwrap()));
+        }
+        assert_eq!(client.num_pending(), 5);
+
+        // receive the responses in the reverse order
+        for (n, receiver) in receivers.into_iter().rev() {
+            let seq = receiver.get_sequence();
+            let (response, header) = async_std::future::timeout(Duration::from_secs(3), receiver)
+                .await
+                .expect("timeout")
+                .unwrap();
+            assert_eq!(response.sum, n);
+            assert_eq!(header.get_sequence(), seq);
+        }
+        assert_eq!(client.num_pending(), 0);
+
+        s.await;
+    });
+
+    Ok(())
+}
+
+#[test]
+fn test_pipelined_client_drain() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_server =
+        ctx.create_node("test_pipelined_drain_server_node", None, Default::default())?;
+    let node_client =
+        ctx.create_node("test_pipelined_drain_client_node", None, Default::default())?;
+
+    let server = common::create_server(node_server, SERVICE_NAME_DRAIN)?;
+    let client = common::create_client(node_client, SERVICE_NAME_DRAIN)?;
+    assert!(client.wait_for_service(Duration::from_secs(3))?);
+
+    let client = client.into_pipelined();
+
+    async_std::task::block_on(async {
+        let s = async_std::task::spawn(async {
+            let _ = async_std::future::timeout(Duration::from_secs(3), run_server(server, 2)).await;
+        });
+
+        let receiver1 = client
+            .send(&AddThreeIntsRequest { a: 1, b: 0, c: 0 })
+            .unwrap();
+        let receiver2 = client
+            .send(&AddThreeIntsRequest { a: 2, b: 0, c: 0 })
+            .unwrap();
+
+        // receiver2 is polled first and waits,
+        // and then receiver1 takes both of the responses
+        let wait2 = async_std::future::timeout(Duration::from_secs(3), receiver2);
+        let drain1 = async {
+            let mut receiver1 = receiver1;
+            loop {
+                match receiver1.try_recv() {
+                    RecvResult::Ok((response, _)) => return response,
+                    RecvResult::RetryLater(r) => receiver1 = r,
+                    RecvResult::Err(e) => panic!("{e}"),
+                }
+                async_std::task::sleep(Duration::from_millis(10)).await;
+            }
+        };
+
+        // receiver2 must be woken up by receiver1
+        let (result2, response1) = futures::join!(wait2, drain1);
+        assert_eq!(response1.sum, 1);
+        assert_eq!(result2.expect("timeout").unwrap().0.sum, 2);
+
+        s.await;
+    });
+
+    Ok(())
+}
+
+async fn run_server(mut server: Server<AddThreeInts>, n: usize) -> Result<(), DynError> {
+    for _ in 0..n {
+        let (sender, request, _) = server.recv().await?;
+        let response = AddThreeIntsResponse {
+            sum: request.a + request.b + request.c,
+        };
+        match sender.send(&response) {
+            Ok(s) => server = s,
+            Err((s, _e)) => server = s.give_up(),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_pipelined_client_timeout() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node(
+        "test_pipelined_client_timeout_node",
+        None,
+        Default::default(),
+    )?;
+
+    // nobody responds
+    let client = common::create_client(node, SERVICE_NAME_TIMEOUT)?.into_pipelined();
+
+    let request = AddThreeIntsRequest { a: 1, b: 2, c: 3 };
+    let receiver1 = client.send(&request)?;
+    let receiver2 = client.send(&request)?;
+    assert_ne!(receiver1.get_sequence(), receiver2.get_sequence());
+
+    let mut selector = ctx.create_selector()?;
+    let receiver1 = match receiver1.recv_timeout(Duration::from_millis(50), &mut selector) {
+        RecvResult::RetryLater(receiver) => receiver,
+        _ => panic!("unexpected response"),
+    };
+
+    let result = async_std::task::block_on(async_std::future::timeout(
+        Duration::from_millis(50),
+        receiver2,
+    ));
+    assert!(result.is_err());
+
+    // timed out requests are forgotten by dropping the receivers
+    assert_eq!(client.num_pending(), 1);
+    drop(receiver1);
+    assert_eq!(client.num_pending(), 0);
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+pub mod common;
+
+use common::msgs::example_msg::msg::Num;
+use safe_drive::{
+    self,
+    context::Context,
+    qos::{policy::LivelinessPolicy, Profile},
+    topic::event::{EventStatus, SubscriberEventType},
+};
+use std::{
+    error::Error,
+    thread,
+    time::{Duration, Instant},
+};
+
+#[cfg(any(feature = "humble", feature = "iron"))]
+const TOPIC_NAME_ACK: &str = "test_publisher_ack";
+const TOPIC_NAME_LIVELINESS: &str = "test_publisher_liveliness";
+
+#[cfg(any(feature = "humble", feature = "iron"))]
+#[test]
+fn test_wait_for_all_acked() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    use safe_drive::qos::policy::ReliabilityPolicy;
+
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_publisher_ack_node", None, Default::default())?;
+
+    let mut qos = Profile::default();
+    qos.reliability = ReliabilityPolicy::Reliable;
+
+    let publisher = node.create_publisher::<Num>(
+        TOPIC_NAME_ACK,
+        Some(qos.clone()),
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        true,
+    )?;
+    let _subscriber = node.create_subscriber::<Num>(
+        TOPIC_NAME_ACK,
+        Some(qos),
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        true,
+    )?;
+
+    publisher.wait_for_subscribers(1, Duration::from_secs(3))?;
+
+    let mut msg = Num::new().unwrap();
+    msg.num = 10;
+    publisher.send(&msg)?;
+
+    assert!(publisher.wait_for_all_acked(Duration::from_secs(3))?);
+
+    Ok(())
+}
+
+#[test]
+fn test_assert_liveliness() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_publisher_liveliness_node", None, Default::default())?;
+
+    let mut qos = Profile::default();
+    qos.liveliness = LivelinessPolicy::ManualByTopic;
+    qos.liveliness_lease_duration = Duration::from_millis(500);
+
+    let publisher = node.create_publisher::<Num>(
+        TOPIC_NAME_LIVELINESS,
+        Some(qos.clone()),
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        true,
+    )?;
+    let subscriber = node.create_subscriber::<Num>(
+        TOPIC_NAME_LIVELINESS,
+        Some(qos),
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        true,
+    )?;
+    let event = subscriber.create_event(SubscriberEventType::LivelinessChanged)?;
+
+    assert!(publisher.wait_for_subscribers(1, Duration::from_secs(3))?);
+
+    // the publisher is alive while asserting the liveliness within the lease duration
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_millis(1500) {
+        publisher.assert_liveliness()?;
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    let EventStatus::LivelinessChanged(status) = event.take()? else {
+        panic!("unexpected event status");
+    };
+    assert_eq!(status.alive_count, 1);
+    assert_eq!(status.not_alive_count, 0);
+
+    // the publisher is not alive after the lease duration without assertions
+    thread::sleep(Duration::from_millis(1500));
+
+    let EventStatus::LivelinessChanged(status) = event.take()? else {
+        panic!("unexpected event status");
+    };
+    assert_eq!(status.alive_count, 0);
+    assert_eq!(status.not_alive_count, 1);
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+pub mod common;
+
+use common::msgs::example_msg::msg::Num;
+use safe_drive::{
+    self,
+    context::{Context, ContextBuilder, ContextShutdown},
+    qos::{policy::ReliabilityPolicy, Profile},
+    topic::event::{EventStatus, PublisherEventType, SubscriberEventType},
+};
+use std::{
+    cell::Cell,
+    error::Error,
+    rc::Rc,
+    thread,
+    time::{Duration, Instant},
+};
+
+const TOPIC_NAME_INCOMPATIBLE: &str = "test_qos_event_incompatible";
+const TOPIC_NAME_DEADLINE: &str = "test_qos_event_deadline";
+const TOPIC_NAME_SHUTDOWN: &str = "test_qos_event_shutdown";
+
+#[test]
+fn test_incompatible_qos_event() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_qos_event_incompatible_node", None, Default::default())?;
+
+    // a reliable subscriber cannot receive messages from a best effort publisher
+    let mut qos_pub = Profile::default();
+    qos_pub.reliability = ReliabilityPolicy::BestEffort;
+    let mut qos_sub = Profile::default();
+    qos_sub.reliability = ReliabilityPolicy::Reliable;
+
+    let publisher = node.create_publisher::<Num>(
+        TOPIC_NAME_INCOMPATIBLE,
+        Some(qos_pub),
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        true,
+    )?;
+    let subscriber = node.create_subscriber::<Num>(
+        TOPIC_NAME_INCOMPATIBLE,
+        Some(qos_sub),
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        true,
+    )?;
+
+    let event_pub = publisher.create_event(PublisherEventType::OfferedIncompatibleQoS)?;
+    let event_sub = subscriber.create_event(SubscriberEventType::RequestedIncompatibleQoS)?;
+
+    let is_offered = Rc::new(Cell::new(false));
+    let is_requested = Rc::new(Cell::new(false));
+    let is_offered_cloned = is_offered.clone();
+    let is_requested_cloned = is_requested.clone();
+
+    let mut selector = ctx.create_selector()?;
+    assert!(selector.add_event_handler(
+        event_pub,
+        Box::new(move |status| {
+            if let EventStatus::OfferedIncompatibleQoS(status) = status {
+                assert!(status.total_count > 0);
+                is_offered_cloned.set(true);
+            }
+        }),
+    ));
+    assert!(selector.add_event_handler(
+        event_sub,
+        Box::new(move |status| {
+            if let EventStatus::RequestedIncompatibleQoS(status) = status {
+                assert!(status.total_count > 0);
+                is_requested_cloned.set(true);
+            }
+        }),
+    ));
+
+    let start = Instant::now();
+    while !(is_offered.get() && is_requested.get()) && start.elapsed() < Duration::from_secs(3) {
+        selector.wait_timeout(Duration::from_millis(100))?;
+    }
+
+    assert!(is_offered.get());
+    assert!(is_requested.get());
+
+    Ok(())
+}
+
+#[test]
+fn test_deadline_missed_event_async() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_qos_event_deadline_node", None, Default::default())?;
+
+    let mut qos = Profile::default();
+    qos.deadline = Duration::from_millis(50);
+
+    let publisher = node.create_publisher::<Num>(
+        TOPIC_NAME_DEADLINE,
+        Some(qos.clone()),
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        true,
+    )?;
+    let subscriber = node.create_subscriber::<Num>(
+        TOPIC_NAME_DEADLINE,
+        Some(qos),
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        true,
+    )?;
+
+    let mut event = subscriber.create_event(SubscriberEventType::RequestedDeadlineMissed)?;
+
+    // no message is sent after the first one, so the deadline must be missed
+    publisher.wait_for_subscribers(1, Duration::from_secs(3))?;
+    let mut msg = Num::new().unwrap();
+    msg.num = 0;
+    publisher.send(&msg)?;
+
+    let status = async_std::task::block_on(async {
+        async_std::future::timeout(Duration::from_secs(3), event.recv())
+            .await
+            .expect("timeout")
+    })?;
+
+    match status {
+        EventStatus::RequestedDeadlineMissed(status) => assert!(status.total_count > 0),
+        _ => panic!("unexpected status: {status:?}"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_event_async_shutdown() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = ContextBuilder::new()
+        .args(["test_event_async_shutdown"])
+        .build()?;
+    let node = ctx.create_node("test_qos_event_shutdown_node", None, Default::default())?;
+    let subscriber = common::create_subscriber(node, TOPIC_NAME_SHUTDOWN, true)?;
+
+    // no publisher exists, so the liveliness never changes
+    let mut event = subscriber.create_event(SubscriberEventType::LivelinessChanged)?;
+
+    let ctx2 = ctx.clone();
+    let th = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(200));
+        ctx2.shutdown()
+    });
+
+    // shutting down the context wakes up the receiver
+    let result = async_std::task::block_on(async {
+        async_std::future::timeout(Duration::from_secs(3), event.recv())
+            .await
+            .expect("timeout")
+    });
+    assert!(result.unwrap_err().is::<ContextShutdown>());
+
+    th.join().unwrap()?;
+
+    Ok(())
+}
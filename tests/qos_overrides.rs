@@ -0,0 +1,155 @@
+pub mod common;
+
+use common::msgs::example_msg::msg::Num;
+use safe_drive::{
+    self,
+    context::Context,
+    node::NodeOptions,
+    parameter::Value,
+    qos::{
+        overriding::QoSOverridingOptions,
+        policy::{PolicyKind, ReliabilityPolicy},
+    },
+};
+use std::error::Error;
+
+const TOPIC_NAME: &str = "test_qos_overrides";
+
+#[test]
+fn test_qos_overrides() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let options = NodeOptions::builder()
+        .parameter("qos_overrides./test_qos_overrides.publisher.depth", "3")
+        .parameter(
+            "qos_overrides./test_qos_overrides.subscription.reliability",
+            "best_effort",
+        )
+        .build()?;
+    let node = ctx.create_node("test_qos_overrides_node", None, options)?;
+
+    let overrides = node.get_parameter_overrides()?;
+    assert!(matches!(
+        overrides.get("qos_overrides./test_qos_overrides.publisher.depth"),
+        Some(Value::I64(3))
+    ));
+
+    let publisher = node.create_publisher_with_qos_overrides::<Num>(
+        TOPIC_NAME,
+        None,
+        &QoSOverridingOptions::with_default_policies(),
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        true,
+    )?;
+    let actual = publisher.get_actual_qos()?;
+    assert_eq!(actual.depth, 3);
+    assert_eq!(actual.reliability, ReliabilityPolicy::Reliable);
+
+    let subscriber = node.create_subscriber_with_qos_overrides::<Num>(
+        TOPIC_NAME,
+        None,
+        &QoSOverridingOptions::with_default_policies(),
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        true,
+    )?;
+    assert_eq!(
+        subscriber.get_actual_qos()?.reliability,
+        ReliabilityPolicy::BestEffort
+    );
+
+    // policies which are not allowed are not overridden
+    let subscriber = node.create_subscriber_with_qos_overrides::<Num>(
+        TOPIC_NAME,
+        None,
+        &QoSOverridingOptions::new(&[PolicyKind::Depth]),
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        true,
+    )?;
+    assert_eq!(
+        subscriber.get_actual_qos()?.reliability,
+        ReliabilityPolicy::Reliable
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_qos_overrides_invalid() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let options = NodeOptions::builder()
+        .parameter(
+            "qos_overrides./test_qos_overrides_invalid.publisher_my_id.reliability",
+            "unreliable",
+        )
+        .build()?;
+    let node = ctx.create_node("test_qos_overrides_invalid_node", None, options)?;
+
+    let result = node.create_publisher_with_qos_overrides::<Num>(
+        "test_qos_overrides_invalid",
+        None,
+        &QoSOverridingOptions::with_default_policies().id("my_id"),
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        true,
+    );
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_qos_overrides_parameter_server() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let options = NodeOptions::builder()
+        .parameter(
+            "qos_overrides./test_qos_overrides_param.publisher.depth",
+            "3",
+        )
+        .parameter(
+            "qos_overrides./test_qos_overrides_param.publisher.reliability",
+            "best_effort",
+        )
+        .build()?;
+    let node = ctx.create_node("test_qos_overrides_param_node", None, options)?;
+
+    // parameters of the parameter server take precedence over the overrides
+    let param_server = node.create_parameter_server()?;
+    param_server.params.write().set_parameter(
+        "qos_overrides./test_qos_overrides_param.publisher.depth".to_string(),
+        Value::I64(5),
+        false,
+        None,
+    )?;
+
+    let publisher = node.create_publisher_with_qos_overrides::<Num>(
+        "test_qos_overrides_param",
+        None,
+        &QoSOverridingOptions::with_default_policies(),
+        #[cfg(not(any(feature = "humble", feature = "galactic")))]
+        true,
+    )?;
+    let actual = publisher.get_actual_qos()?;
+    assert_eq!(actual.depth, 5);
+    assert_eq!(actual.reliability, ReliabilityPolicy::BestEffort);
+
+    // the resulting policies are declared as read-only parameters
+    let mut params = param_server.params.write();
+    let history = params
+        .get_parameter("qos_overrides./test_qos_overrides_param.publisher.history")
+        .unwrap();
+    assert!(history.descriptor.read_only);
+    assert_eq!(history.value, Value::String("keep_last".to_string()));
+
+    let name = "qos_overrides./test_qos_overrides_param.publisher.reliability";
+    let reliability = params.get_parameter(name).unwrap();
+    assert!(reliability.descriptor.read_only);
+    assert_eq!(reliability.value, Value::String("best_effort".to_string()));
+
+    let result = params.set_parameter(
+        name.to_string(),
+        Value::String("reliable".to_string()),
+        false,
+        None,
+    );
+    assert!(result.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use safe_drive::qos::{
+    policy::{DurabilityPolicy, ReliabilityPolicy},
+    Compatibility, Profile,
+};
+use std::error::Error;
+
+#[test]
+fn test_check_compatible() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    assert_eq!(
+        Profile::check_compatible(&Profile::default(), &Profile::default())?,
+        Compatibility::Ok
+    );
+
+    // a best effort subscriber can receive messages from a reliable publisher
+    assert_eq!(
+        Profile::check_compatible(&Profile::default(), &Profile::clock())?,
+        Compatibility::Ok
+    );
+
+    // a reliable subscriber cannot receive messages from a best effort publisher
+    match Profile::check_compatible(&Profile::sensor_data(), &Profile::parameter_events())? {
+        Compatibility::Error(reason) => assert!(!reason.is_empty()),
+        result => panic!("unexpected result: {result:?}"),
+    }
+
+    // a transient local subscriber cannot receive messages from a volatile publisher
+    assert!(matches!(
+        Profile::check_compatible(&Profile::default(), &Profile::rosout())?,
+        Compatibility::Error(_)
+    ));
+
+    // system default policies cannot be checked
+    match Profile::check_compatible(&Profile::system_default(), &Profile::default())? {
+        Compatibility::Warning(reason) => assert!(!reason.is_empty()),
+        result => panic!("unexpected result: {result:?}"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_presets() {
+    let rosout = Profile::rosout();
+    assert_eq!(rosout.durability, DurabilityPolicy::TransientLocal);
+    assert_eq!(rosout.depth, 1000);
+
+    let clock = Profile::clock();
+    assert_eq!(clock.reliability, ReliabilityPolicy::BestEffort);
+    assert_eq!(clock.depth, 1);
+
+    #[cfg(not(any(feature = "humble", feature = "galactic")))]
+    {
+        let best_available = Profile::best_available();
+        assert_eq!(best_available.reliability, ReliabilityPolicy::BestAvailable);
+        assert_eq!(best_available.durability, DurabilityPolicy::BestAvailable);
+    }
+}
@@ -0,0 +1,88 @@
+pub mod common;
+
+use common::msgs::example_msg::msg::Num;
+use safe_drive::{
+    self,
+    context::Context,
+    topic::serialized::{deserialize, serialize, SerializedMessage},
+    RecvResult,
+};
+use std::{error::Error, thread, time::Duration};
+
+const TOPIC_NAME: &str = "test_serialized";
+const TOPIC_NAME_ASYNC: &str = "test_serialized_async";
+
+#[test]
+fn test_serialize() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let msg = Num { num: 100 };
+
+    let serialized = serialize(&msg)?;
+    assert!(!serialized.is_empty());
+
+    // copy the bytes and deserialize them
+    let copied = SerializedMessage::from_bytes(serialized.as_bytes())?;
+    assert_eq!(copied.as_bytes(), serialized.as_bytes());
+
+    let msg: Num = deserialize(&copied)?;
+    assert_eq!(msg.num, 100);
+
+    Ok(())
+}
+
+#[test]
+fn test_pubsub_serialized() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_pub = ctx.create_node("test_serialized_pub_node", None, Default::default())?;
+    let node_sub = ctx.create_node("test_serialized_sub_node", None, Default::default())?;
+
+    let publisher = common::create_publisher(node_pub, TOPIC_NAME, true)?;
+    let subscriber = common::create_subscriber(node_sub, TOPIC_NAME, true)?;
+    assert!(publisher.wait_for_subscribers(1, Duration::from_secs(3))?);
+
+    // send a serialized message
+    let msg = Num { num: 200 };
+    publisher.send_serialized(&serialize(&msg)?)?;
+
+    // receive the serialized message
+    let mut received = None;
+    for _ in 0..10 {
+        match subscriber.try_recv_serialized() {
+            RecvResult::Ok(msg) => {
+                received = Some(msg);
+                break;
+            }
+            RecvResult::RetryLater(_) => thread::sleep(Duration::from_millis(100)),
+            RecvResult::Err(e) => return Err(e),
+        }
+    }
+
+    let msg: Num = deserialize(&received.expect("timeout"))?;
+    assert_eq!(msg.num, 200);
+
+    Ok(())
+}
+
+#[test]
+fn test_recv_serialized_async() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_pub = ctx.create_node("test_serialized_async_pub_node", None, Default::default())?;
+    let node_sub = ctx.create_node("test_serialized_async_sub_node", None, Default::default())?;
+
+    let publisher = common::create_publisher(node_pub, TOPIC_NAME_ASYNC, true)?;
+    let mut subscriber = common::create_subscriber(node_sub, TOPIC_NAME_ASYNC, true)?;
+    assert!(publisher.wait_for_subscribers(1, Duration::from_secs(3))?);
+
+    let msg = Num { num: 300 };
+    publisher.send(&msg)?;
+
+    let received = async_std::task::block_on(async {
+        async_std::future::timeout(Duration::from_secs(3), subscriber.recv_serialized())
+            .await
+            .expect("timeout")
+    })?;
+
+    let msg: Num = deserialize(&received)?;
+    assert_eq!(msg.num, 300);
+
+    Ok(())
+}
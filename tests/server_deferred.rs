@@ -0,0 +1,71 @@
+pub mod common;
+
+use common::msgs::example_msg::srv::{AddThreeInts, AddThreeIntsRequest, AddThreeIntsResponse};
+use safe_drive::{self, context::Context, service::server::Responder, RecvResult};
+use std::{cell::RefCell, error::Error, rc::Rc, time::Duration};
+
+const SERVICE_NAME: &str = "test_server_deferred";
+
+#[test]
+fn test_server_deferred() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_server = ctx.create_node("test_server_deferred_node", None, Default::default())?;
+    let node_client = ctx.create_node("test_client_deferred_node", None, Default::default())?;
+
+    let server = common::create_server(node_server, SERVICE_NAME)?;
+    let client = common::create_client(node_client, SERVICE_NAME)?;
+    assert!(client.wait_for_service(Duration::from_secs(3))?);
+
+    let client = client.into_pipelined();
+
+    // store requests and responders
+    type Deferred = Vec<(AddThreeIntsRequest, Responder<AddThreeInts>)>;
+    let deferred: Rc<RefCell<Deferred>> = Default::default();
+    let deferred_cloned = deferred.clone();
+
+    let mut selector = ctx.create_selector()?;
+    assert!(selector.add_server_deferred(
+        server,
+        Box::new(move |request, _header, responder| {
+            deferred_cloned.borrow_mut().push((request, responder));
+        }),
+    ));
+
+    let receivers = (0..2)
+        .map(|n| client.send(&AddThreeIntsRequest { a: n, b: 1, c: 1 }))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for _ in 0..10 {
+        if deferred.borrow().len() == 2 {
+            break;
+        }
+        selector.wait_timeout(Duration::from_millis(100))?;
+    }
+
+    // respond later in the reverse order
+    for (request, responder) in deferred.borrow_mut().drain(..).rev() {
+        let response = AddThreeIntsResponse {
+            sum: request.a + request.b + request.c,
+        };
+        assert!(responder.send(&response).is_ok());
+    }
+
+    let mut selector_client = ctx.create_selector()?;
+    for (n, mut receiver) in (0..2).zip(receivers) {
+        let mut received = false;
+        for _ in 0..10 {
+            match receiver.recv_timeout(Duration::from_millis(100), &mut selector_client) {
+                RecvResult::Ok((response, _header)) => {
+                    assert_eq!(response.sum, n + 2);
+                    received = true;
+                    break;
+                }
+                RecvResult::RetryLater(r) => receiver = r,
+                RecvResult::Err(e) => return Err(e),
+            }
+        }
+        assert!(received);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+#![cfg(not(any(feature = "humble", feature = "galactic")))]
+
+pub mod common;
+
+use common::msgs::example_msg::srv::{AddThreeIntsRequest, AddThreeIntsResponse};
+use safe_drive::{
+    self, context::Context, node::Node, parameter::Value,
+    service::introspection::ServiceIntrospectionState,
+};
+use std::{error::Error, thread, time::Duration};
+
+const SERVICE_NAME: &str = "test_service_introspection";
+const SERVICE_NAME_THREADS: &str = "test_service_introspection_threads";
+const EVENT_TOPIC_NAME: &str = "/test_service_introspection/_service_event";
+
+/// Wait until the number of publishers of the event topic becomes `n`.
+fn wait_event_publishers(node: &Node, n: usize) -> Result<bool, Box<dyn Error + Sync + Send>> {
+    for _ in 0..20 {
+        if node.count_publishers(EVENT_TOPIC_NAME)? == n {
+            return Ok(true);
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    Ok(false)
+}
+
+#[test]
+fn test_service_introspection() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_service_introspection_node", None, Default::default())?;
+
+    let server = common::create_server(node.clone(), SERVICE_NAME)?;
+    let client = common::create_client(node.clone(), SERVICE_NAME)?;
+    assert!(wait_event_publishers(&node, 0)?);
+
+    server.configure_introspection(None, ServiceIntrospectionState::Contents)?;
+    client.configure_introspection(None, ServiceIntrospectionState::Metadata)?;
+    assert!(wait_event_publishers(&node, 2)?);
+
+    // configure after moving the server
+    let introspection = server.get_introspection();
+    let mut selector = ctx.create_selector()?;
+    selector.add_server(
+        server,
+        Box::new(|request, _header| AddThreeIntsResponse {
+            sum: request.a + request.b + request.c,
+        }),
+    );
+
+    assert!(introspection
+        .configure_by_parameter(None, &Value::String("invalid".to_string()))
+        .is_err());
+    introspection.configure_by_parameter(None, &Value::String("off".to_string()))?;
+    client.configure_introspection(None, ServiceIntrospectionState::Off)?;
+    assert!(wait_event_publishers(&node, 0)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_service_introspection_threads() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node(
+        "test_service_introspection_threads_node",
+        None,
+        Default::default(),
+    )?;
+
+    let server = common::create_server(node.clone(), SERVICE_NAME_THREADS)?;
+    let client = common::create_client(node, SERVICE_NAME_THREADS)?;
+    assert!(client.wait_for_service(Duration::from_secs(3))?);
+
+    // configure introspection while sending requests and responses
+    let introspection = server.get_introspection();
+    let th = thread::spawn(move || {
+        for i in 0..50 {
+            let state = if i % 2 == 0 {
+                ServiceIntrospectionState::Contents
+            } else {
+                ServiceIntrospectionState::Off
+            };
+            introspection.configure(None, state).unwrap();
+        }
+    });
+
+    let mut server = server.into_concurrent();
+    let client = client.into_pipelined();
+
+    async_std::task::block_on(async {
+        for n in 0..10 {
+            let request = AddThreeIntsRequest { a: n, b: 0, c: 0 };
+            let receiver = client.send(&request).unwrap();
+
+            let (responder, request, _header) =
+                async_std::future::timeout(Duration::from_secs(3), server.recv())
+                    .await
+                    .expect("timeout")
+                    .unwrap();
+            let response = AddThreeIntsResponse { sum: request.a };
+            thread::spawn(move || responder.send(&response).map_err(|(_, e)| e))
+                .join()
+                .unwrap()
+                .unwrap();
+
+            let (response, _header) = async_std::future::timeout(Duration::from_secs(3), receiver)
+                .await
+                .expect("timeout")
+                .unwrap();
+            assert_eq!(response.sum, n);
+        }
+    });
+
+    th.join().unwrap();
+
+    Ok(())
+}
+
+#[test]
+fn test_service_introspection_state() {
+    for state in [
+        ServiceIntrospectionState::Off,
+        ServiceIntrospectionState::Metadata,
+        ServiceIntrospectionState::Contents,
+    ] {
+        assert_eq!(
+            state
+                .to_string()
+                .parse::<ServiceIntrospectionState>()
+                .unwrap(),
+            state
+        );
+    }
+    assert!("on".parse::<ServiceIntrospectionState>().is_err());
+}
@@ -0,0 +1,103 @@
+pub mod common;
+
+use safe_drive::{
+    context::{ContextBuilder, ContextShutdown},
+    error::RCLError,
+};
+use std::{
+    error::Error,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+const TOPIC_NAME: &str = "test_shutdown";
+const TOPIC_NAME_DROP: &str = "test_shutdown_drop";
+
+#[test]
+fn test_shutdown() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = ContextBuilder::new().args(["test_shutdown"]).build()?;
+    assert!(ctx.is_valid());
+
+    // register callbacks
+    let count = Arc::new(AtomicUsize::new(0));
+
+    let count1 = count.clone();
+    let ctx1 = ctx.clone();
+    ctx.add_on_shutdown(Box::new(move || {
+        // the context is still valid in callbacks
+        assert!(ctx1.is_valid());
+        assert_eq!(count1.fetch_add(1, Ordering::SeqCst), 0);
+    }));
+
+    let count2 = count.clone();
+    ctx.add_on_shutdown(Box::new(move || {
+        assert_eq!(count2.fetch_add(1, Ordering::SeqCst), 1);
+    }));
+
+    let id = ctx.add_on_shutdown(Box::new(|| panic!("removed callback is invoked")));
+    assert!(ctx.remove_on_shutdown(id));
+    assert!(!ctx.remove_on_shutdown(id));
+
+    // create a selector waiting forever
+    let node = ctx.create_node("test_shutdown_node", None, Default::default())?;
+    let subscriber = common::create_subscriber(node, TOPIC_NAME, true)?;
+
+    let ctx2 = ctx.clone();
+    let th = thread::spawn(move || {
+        let mut selector = ctx2.create_selector().unwrap();
+        selector.add_subscriber(subscriber, Box::new(|_| ()));
+        selector.wait()
+    });
+
+    thread::sleep(Duration::from_millis(100));
+
+    // shutdown wakes up the selector
+    ctx.shutdown()?;
+    assert!(!ctx.is_valid());
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+
+    let result = th.join().unwrap();
+    assert!(result.unwrap_err().is::<ContextShutdown>());
+
+    // shutdown twice
+    assert!(matches!(ctx.shutdown(), Err(RCLError::AlreadyShutdown)));
+
+    Ok(())
+}
+
+#[test]
+fn test_shutdown_on_drop() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = ContextBuilder::new()
+        .args(["test_shutdown_on_drop"])
+        .build()?;
+
+    let is_called = Arc::new(AtomicBool::new(false));
+    let is_called2 = is_called.clone();
+    ctx.add_on_shutdown(Box::new(move || is_called2.store(true, Ordering::SeqCst)));
+
+    // spawn the selector thread for asynchronous receivers
+    let node = ctx.create_node("test_shutdown_on_drop_node", None, Default::default())?;
+    let mut subscriber = common::create_subscriber(node, TOPIC_NAME_DROP, true)?;
+    let result = async_std::task::block_on(async_std::future::timeout(
+        Duration::from_millis(100),
+        subscriber.recv(),
+    ));
+    assert!(result.is_err());
+
+    // the selector thread must not keep the context alive
+    drop(subscriber);
+    drop(ctx);
+
+    for _ in 0..30 {
+        if is_called.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    panic!("on-shutdown callbacks are not invoked");
+}
@@ -0,0 +1,86 @@
+pub mod common;
+
+use common::msgs::example_msg::msg::Num;
+use safe_drive::{self, context::Context, RecvResult};
+use std::{cell::RefCell, error::Error, rc::Rc, thread, time::Duration};
+
+const TOPIC_NAME: &str = "test_try_recv_many";
+const TOPIC_NAME_SELECTOR: &str = "test_add_subscriber_batch";
+
+#[test]
+fn test_try_recv_many() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_pub = ctx.create_node("test_try_recv_many_pub_node", None, Default::default())?;
+    let node_sub = ctx.create_node("test_try_recv_many_sub_node", None, Default::default())?;
+
+    let publisher = common::create_publisher(node_pub, TOPIC_NAME, true)?;
+    let subscriber = common::create_subscriber(node_sub, TOPIC_NAME, true)?;
+    assert!(publisher.wait_for_subscribers(1, Duration::from_secs(3))?);
+
+    assert!(matches!(subscriber.try_recv_many(0), RecvResult::Err(_)));
+    assert!(matches!(
+        subscriber.try_recv_many(4),
+        RecvResult::RetryLater(_)
+    ));
+
+    for n in 0..5 {
+        publisher.send(&Num { num: n })?;
+    }
+    thread::sleep(Duration::from_millis(100));
+
+    // take 3 messages, and then the remaining 2 messages
+    let mut received = Vec::new();
+    for len in [3, 2] {
+        match subscriber.try_recv_many(3) {
+            RecvResult::Ok(msgs) => {
+                assert_eq!(msgs.len(), len);
+                received.extend(msgs.iter().map(|msg| msg.num));
+            }
+            _ => panic!("failed to receive messages"),
+        }
+    }
+    assert_eq!(received, vec![0, 1, 2, 3, 4]);
+
+    Ok(())
+}
+
+#[test]
+fn test_add_subscriber_batch() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_pub = ctx.create_node("test_subscriber_batch_pub_node", None, Default::default())?;
+    let node_sub = ctx.create_node("test_subscriber_batch_sub_node", None, Default::default())?;
+
+    let publisher = common::create_publisher(node_pub, TOPIC_NAME_SELECTOR, true)?;
+    let subscriber = common::create_subscriber(node_sub, TOPIC_NAME_SELECTOR, true)?;
+    assert!(publisher.wait_for_subscribers(1, Duration::from_secs(3))?);
+
+    for n in 0..5 {
+        publisher.send(&Num { num: n })?;
+    }
+    thread::sleep(Duration::from_millis(100));
+
+    let received: Rc<RefCell<Vec<i64>>> = Default::default();
+    let received_cloned = received.clone();
+
+    let mut selector = ctx.create_selector()?;
+    selector.add_subscriber_batch(
+        subscriber,
+        8,
+        Box::new(move |msgs| {
+            assert!(!msgs.is_empty() && msgs.len() <= 8);
+            received_cloned
+                .borrow_mut()
+                .extend(msgs.iter().map(|msg| msg.num));
+        }),
+    );
+
+    for _ in 0..5 {
+        if received.borrow().len() == 5 {
+            break;
+        }
+        selector.wait_timeout(Duration::from_secs(1))?;
+    }
+    assert_eq!(*received.borrow(), vec![0, 1, 2, 3, 4]);
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+pub mod common;
+
+use common::msgs::example_msg::msg::Num;
+use futures::stream::FusedStream;
+use futures::StreamExt;
+use safe_drive::{
+    self,
+    context::{Context, ContextBuilder},
+    topic::publisher::Publisher,
+};
+use std::{error::Error, thread, time::Duration};
+
+const TOPIC_NAME: &str = "test_subscriber_stream";
+const TOPIC_NAME_SHUTDOWN: &str = "test_subscriber_stream_shutdown";
+
+#[test]
+fn test_subscriber_stream() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_pub = ctx.create_node("test_stream_pub_node", None, Default::default())?;
+    let node_sub = ctx.create_node("test_stream_sub_node", None, Default::default())?;
+
+    let p = common::create_publisher(node_pub, TOPIC_NAME, true)?;
+    let s = common::create_subscriber(node_sub, TOPIC_NAME, true)?;
+    p.wait_for_subscribers(1, Duration::from_secs(3))?;
+
+    let received = async_std::task::block_on(async {
+        let p = async_std::task::spawn(run_publisher(p));
+
+        let received: Vec<i64> = async_std::future::timeout(
+            Duration::from_secs(3),
+            s.into_stream()
+                .take(3)
+                .map(|msg| msg.unwrap().num)
+                .collect(),
+        )
+        .await
+        .expect("timeout");
+
+        p.await;
+        received
+    });
+
+    assert_eq!(received, vec![0, 1, 2]);
+
+    Ok(())
+}
+
+#[test]
+fn test_subscriber_stream_shutdown() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = ContextBuilder::new()
+        .args(["test_subscriber_stream_shutdown"])
+        .build()?;
+    let node = ctx.create_node("test_stream_shutdown_node", None, Default::default())?;
+    let s = common::create_subscriber(node, TOPIC_NAME_SHUTDOWN, true)?;
+
+    let ctx2 = ctx.clone();
+    let th = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(200));
+        ctx2.shutdown()
+    });
+
+    // the stream ends without errors when the context is shut down
+    let mut stream = s.into_stream();
+    let next = async_std::task::block_on(async_std::future::timeout(
+        Duration::from_secs(3),
+        stream.next(),
+    ))
+    .expect("timeout");
+    assert!(next.is_none());
+    assert!(stream.is_terminated());
+
+    th.join().unwrap()?;
+
+    Ok(())
+}
+
+async fn run_publisher(p: Publisher<Num>) {
+    for n in 0..3 {
+        let msg = Num { num: n };
+        p.send(&msg).unwrap();
+        async_std::task::sleep(Duration::from_millis(100)).await;
+    }
+}
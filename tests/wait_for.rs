@@ -0,0 +1,86 @@
+pub mod common;
+
+use safe_drive::{self, context::Context};
+use std::{error::Error, thread, time::Duration};
+
+const TOPIC_NAME: &str = "test_wait_for_subscribers";
+const SERVICE_NAME: &str = "test_wait_for_service";
+const SERVICE_NAME_ASYNC: &str = "test_wait_for_service_async";
+
+#[test]
+fn test_wait_for_service() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_server = ctx.create_node("test_wait_for_server_node", None, Default::default())?;
+    let node_client = ctx.create_node("test_wait_for_client_node", None, Default::default())?;
+
+    let client = common::create_client(node_client, SERVICE_NAME)?;
+
+    // no server
+    assert!(!client.is_service_available()?);
+    assert!(!client.wait_for_service(Duration::from_millis(100))?);
+
+    // create a server later
+    let th = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        common::create_server(node_server, SERVICE_NAME).unwrap()
+    });
+
+    assert!(client.wait_for_service(Duration::from_secs(3))?);
+    assert!(client.is_service_available()?);
+
+    let _server = th.join().unwrap();
+
+    Ok(())
+}
+
+#[test]
+fn test_wait_for_service_async() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_server =
+        ctx.create_node("test_wait_for_server_async_node", None, Default::default())?;
+    let node_client =
+        ctx.create_node("test_wait_for_client_async_node", None, Default::default())?;
+
+    let client = common::create_client(node_client, SERVICE_NAME_ASYNC)?;
+
+    let th = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        common::create_server(node_server, SERVICE_NAME_ASYNC).unwrap()
+    });
+
+    async_std::task::block_on(async {
+        async_std::future::timeout(Duration::from_secs(3), client.wait_for_service_async())
+            .await
+            .expect("timeout")
+    })?;
+
+    let _server = th.join().unwrap();
+
+    Ok(())
+}
+
+#[test]
+fn test_wait_for_subscribers() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_pub = ctx.create_node("test_wait_for_pub_node", None, Default::default())?;
+    let node_sub = ctx.create_node("test_wait_for_sub_node", None, Default::default())?;
+
+    let publisher = common::create_publisher(node_pub, TOPIC_NAME, true)?;
+
+    assert!(!publisher.wait_for_subscribers(1, Duration::from_millis(100))?);
+
+    let _subscriber = common::create_subscriber(node_sub.clone(), TOPIC_NAME, true)?;
+    assert!(publisher.wait_for_subscribers(1, Duration::from_secs(3))?);
+
+    let _subscriber2 = common::create_subscriber(node_sub, TOPIC_NAME, true)?;
+    async_std::task::block_on(async {
+        async_std::future::timeout(
+            Duration::from_secs(3),
+            publisher.wait_for_subscribers_async(2),
+        )
+        .await
+        .expect("timeout")
+    })?;
+
+    Ok(())
+}